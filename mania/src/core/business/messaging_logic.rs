@@ -1,5 +1,7 @@
 use crate::core::business::{BusinessError, LogicRegistry};
 use crate::core::business::{BusinessHandle, LogicFlow};
+use crate::core::error::ManiaResult;
+use crate::core::event::message::multi_msg_upload::MultiMsgUploadEvent;
 use crate::core::event::message::push_msg::PushMessageEvent;
 use crate::core::event::message::send_message::SendMessageEvent;
 use crate::core::event::notify::bot_sys_rename::BotSysRenameEvent;
@@ -11,6 +13,7 @@ use crate::core::event::notify::friend_sys_request::FriendSysRequestEvent;
 use crate::core::event::notify::group_sys_admin::GroupSysAdminEvent;
 use crate::core::event::notify::group_sys_decrease::GroupSysDecreaseEvent;
 use crate::core::event::notify::group_sys_essence::GroupSysEssenceEvent;
+use crate::core::event::notify::group_sys_grey_tip::GroupSysGreyTipEvent;
 use crate::core::event::notify::group_sys_increase::GroupSysIncreaseEvent;
 use crate::core::event::notify::group_sys_invite::GroupSysInviteEvent;
 use crate::core::event::notify::group_sys_member_enter::GroupSysMemberEnterEvent;
@@ -36,25 +39,30 @@ use crate::event::group::group_poke::GroupPokeEvent;
 use crate::event::group::group_reaction::GroupReactionEvent;
 use crate::event::group::group_recall::GroupRecallEvent;
 use crate::event::group::{
-    GroupEvent, group_admin_changed, group_essence, group_invitation, group_invitation_request,
-    group_join_request, group_member_decrease, group_member_enter, group_member_increase,
-    group_member_mute, group_message, group_mute, group_name_change, group_pin_changed,
-    group_special_title, group_todo,
+    GroupEvent, group_admin_changed, group_essence, group_grey_tip, group_invitation,
+    group_invitation_request, group_join_request, group_member_decrease, group_member_enter,
+    group_member_increase, group_member_mute, group_message, group_mute, group_name_change,
+    group_pin_changed, group_special_title, group_todo,
 };
-use crate::event::system::{SystemEvent, bot_rename, temp_message};
+use crate::event::system::{SystemEvent, bot_rename, self_message, temp_message};
 use crate::message::chain::{MessageChain, MessageType};
 use crate::message::entity::Entity;
 use crate::message::entity::file::FileUnique;
+use crate::message::entity::mention::MentionEntity;
+use crate::message::filter::SelfMessageFilter;
+use crate::message::packer::MessagePacker;
 use mania_macros::handle_event;
 use std::sync::Arc;
 
 #[handle_event(
     SendMessageEvent,
+    MultiMsgUploadEvent,
     PushMessageEvent,
     GroupSysRequestJoinEvent,
     GroupSysInviteEvent,
     GroupSysAdminEvent,
     GroupSysPokeEvent,
+    GroupSysGreyTipEvent,
     GroupSysReactionEvent,
     GroupSysRecallEvent,
     GroupSysEssenceEvent,
@@ -96,9 +104,33 @@ async fn messaging_logic_incoming(
     {
         if let Some(msg) = event.as_any_mut().downcast_mut::<PushMessageEvent>() {
             if let Some(mut chain) = msg.chain.take() {
+                let self_message_filter = handle.context.config.self_message_filter;
+                chain.is_self = chain.friend_uin == **handle.context.key_store.uin.load();
+                // dropped before anything is fetched for them
+                if chain.is_self && self_message_filter == SelfMessageFilter::Drop {
+                    return event;
+                }
                 resolve_incoming_chain(&mut chain, handle.clone()).await;
                 resolve_chain_metadata(&mut chain, handle.clone()).await;
-                // TODO: MessageFilter.Filter(push.Chain);
+                if chain.is_self && self_message_filter == SelfMessageFilter::Separate {
+                    if let Err(e) =
+                        handle
+                            .event_dispatcher
+                            .system
+                            .send(Some(SystemEvent::SelfMessageEvent(
+                                self_message::SelfMessageEvent {
+                                    chain: Box::new(chain),
+                                },
+                            )))
+                    {
+                        tracing::error!("Failed to send self_message event: {:?}", e);
+                    }
+                    return event;
+                }
+                // own messages need no read report
+                if handle.context.config.auto_mark_read && !chain.is_self {
+                    mark_chain_read(&chain, &handle);
+                }
                 // TODO?: sb tx! Collection.Invoker.PostEvent(new GroupInvitationEvent(groupUin, chain.FriendUin, sequence));
                 match &chain.typ {
                     MessageType::Group(_) => {
@@ -122,7 +154,7 @@ async fn messaging_logic_incoming(
                             tracing::error!("Failed to send friend_message event: {:?}", e);
                         }
                     }
-                    MessageType::Temp => {
+                    MessageType::Temp(_) => {
                         if let Err(e) = handle.event_dispatcher.system.send(Some(
                             SystemEvent::TempMessageEvent(temp_message::TempMessageEvent { chain }),
                         )) {
@@ -244,6 +276,22 @@ async fn messaging_logic_incoming(
             return event;
         }
 
+        if let Some(tip) = event.as_any_mut().downcast_mut::<GroupSysGreyTipEvent>() {
+            if let Err(e) = handle
+                .event_dispatcher
+                .group
+                .send(Some(GroupEvent::GroupGreyTip(
+                    group_grey_tip::GroupGreyTipEvent {
+                        group_uin: tip.group_uin,
+                        grey_tip: std::mem::take(&mut tip.grey_tip),
+                    },
+                )))
+            {
+                tracing::error!("Failed to send group grey tip event: {:?}", e);
+            }
+            return event;
+        }
+
         if let Some(join) = event.as_any_mut().downcast_mut::<GroupSysIncreaseEvent>() {
             let member_uin = handle.resolve_stranger_uid2uin_fast(&join.member_uid).await;
             let invitor_uin = handle
@@ -381,6 +429,7 @@ async fn messaging_logic_incoming(
                     operator_uin: handle
                         .uid2uin_fast(&todo.operator_uid, Some(todo.group_uin))
                         .await,
+                    action: group_todo::GroupTodoAction::Unknown,
                 })))
             {
                 tracing::error!("Failed to send group todo event: {:?}", e);
@@ -670,7 +719,7 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
                     MessageType::Group(grp) => {
                         handle.download_group_image(grp.group_uin, index_node).await
                     }
-                    MessageType::Friend(_) | MessageType::Temp => {
+                    MessageType::Friend(_) | MessageType::Temp(_) => {
                         handle.download_c2c_image(index_node).await
                     }
                     _ => continue,
@@ -703,6 +752,23 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
                     }
                 }
             }
+            Entity::LongMsg(ref mut long) => {
+                let msg = handle
+                    .multi_msg_download(chain.uid.clone(), long.res_id.clone())
+                    .await;
+                match msg {
+                    Ok(Some(chains)) => match chains.into_iter().next() {
+                        Some(inner) => long.chain = inner,
+                        None => tracing::warn!("Empty chains found in LongMsg {}", long.res_id),
+                    },
+                    Ok(None) => {
+                        tracing::warn!("No chains found in MultiMsgDownloadEvent");
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to download LongMsg: {:?}", e);
+                    }
+                }
+            }
             Entity::File(ref mut file) => {
                 file.file_url = match file.extra.as_ref() {
                     Some(extra) => match extra {
@@ -749,7 +815,7 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
                                 .download_group_record_by_node(grp.group_uin, node())
                                 .await
                         }
-                        MessageType::Friend(_) | MessageType::Temp => {
+                        MessageType::Friend(_) | MessageType::Temp(_) => {
                             handle.download_c2c_record_by_node(node()).await
                         }
                         _ => continue,
@@ -760,7 +826,7 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
                                 .download_group_record_by_uuid(grp.group_uin, Some(uuid.clone()))
                                 .await
                         }
-                        MessageType::Friend(_) | MessageType::Temp => {
+                        MessageType::Friend(_) | MessageType::Temp(_) => {
                             handle.download_c2c_record_by_uuid(Some(uuid.clone())).await
                         }
                         _ => continue,
@@ -816,7 +882,7 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
                             }
                         }
                     }
-                    MessageType::Friend(_) | MessageType::Temp => {
+                    MessageType::Friend(_) | MessageType::Temp(_) => {
                         let self_uid = chain.uid.clone();
                         handle
                             .download_video(
@@ -845,6 +911,19 @@ async fn resolve_incoming_chain(chain: &mut MessageChain, handle: Arc<BusinessHa
     }
 }
 
+/// Replace `chain` with what `pack` makes of it when its packet exceeds
+/// [`crate::ClientConfig::max_message_size`]
+async fn pack_oversized_chain(
+    chain: &mut MessageChain,
+    ctx: &Context,
+    pack: impl AsyncFnOnce(MessageChain) -> ManiaResult<MessageChain>,
+) -> ManiaResult<()> {
+    if MessagePacker::exceeds_size_limit(chain, ctx) {
+        *chain = pack(std::mem::take(chain)).await?;
+    }
+    Ok(())
+}
+
 async fn messaging_logic_outgoing(
     event: &mut dyn ServerEvent,
     handle: Arc<BusinessHandle>,
@@ -853,8 +932,34 @@ async fn messaging_logic_outgoing(
         _ if let Some(send) = event.as_any_mut().downcast_mut::<SendMessageEvent>() => {
             resolve_chain_metadata(&mut send.chain, handle.clone()).await;
             resolve_outgoing_chain(&mut send.chain, handle.clone()).await?;
+            // measured after the upload, as resources are only packed with their `msg_info`
+            pack_oversized_chain(&mut send.chain, &handle.context, async |chain| {
+                handle.pack_long_message(chain).await
+            })
+            .await
+            .map_err(|e| BusinessError::GenericError(e.to_string()))?;
             // TODO: await Collection.Highway.UploadResources(send.Chain);
         }
+        _ if let Some(multi) = event.as_any_mut().downcast_mut::<MultiMsgUploadEvent>() => {
+            // resources inside the nodes are uploaded to where the bundle is going to be sent
+            let self_uid = handle
+                .context
+                .key_store
+                .uid
+                .load()
+                .as_deref()
+                .cloned()
+                .unwrap_or_default();
+            for chain in multi.chains.iter_mut() {
+                resolve_outgoing_entities(
+                    &mut chain.entities,
+                    multi.group_uin,
+                    &self_uid,
+                    handle.clone(),
+                )
+                .await?;
+            }
+        }
         _ => {}
     }
     Ok(event)
@@ -865,48 +970,182 @@ async fn resolve_outgoing_chain(
     chain: &mut MessageChain,
     handle: Arc<BusinessHandle>,
 ) -> Result<(), BusinessError> {
-    let entities: &mut Vec<Entity> = chain.entities.as_mut();
+    let group_uin = match &chain.typ {
+        MessageType::Group(grp) => Some(grp.group_uin),
+        MessageType::Friend(_) | MessageType::Temp(_) => None,
+        MessageType::None => return Ok(()),
+    };
+    resolve_outgoing_entities(&mut chain.entities, group_uin, &chain.uid, handle).await
+}
+
+/// Upload the resources of outgoing entities, to the group if `group_uin` is present,
+/// otherwise to the c2c session of `uid`
+///
+/// Media that already carries a `msg_info` was uploaded before (e.g. restored from a serialized
+/// chain, or resolved once already when the chain is measured and then repacked as a long
+/// message), it is sent as is instead of being uploaded again.
+async fn resolve_outgoing_entities(
+    entities: &mut [Entity],
+    group_uin: Option<u32>,
+    uid: &str,
+    handle: Arc<BusinessHandle>,
+) -> Result<(), BusinessError> {
     for entity in entities {
         match entity {
-            Entity::Image(image) => match &chain.typ {
-                MessageType::Group(grp) => handle
-                    .upload_group_image(grp.group_uin, image)
+            Entity::Image(image) if image.msg_info.is_none() => match group_uin {
+                Some(group_uin) => handle
+                    .upload_group_image(group_uin, image)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                MessageType::Friend(_) | MessageType::Temp => handle
-                    .upload_c2c_image(&chain.uid, image)
+                None => handle
+                    .upload_c2c_image(uid, image)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                _ => {}
             },
-            Entity::Video(video) => match &chain.typ {
-                MessageType::Group(grp) => handle
-                    .upload_group_video(grp.group_uin, video)
+            Entity::Video(video) if video.msg_info.is_none() => match group_uin {
+                Some(group_uin) => handle
+                    .upload_group_video(group_uin, video)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                MessageType::Friend(_) | MessageType::Temp => handle
-                    .upload_c2c_video(&chain.uid, video)
+                None => handle
+                    .upload_c2c_video(uid, video)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                _ => {}
             },
-            Entity::Record(record) => match &chain.typ {
-                MessageType::Group(grp) => handle
-                    .upload_group_record(grp.group_uin, record)
+            Entity::Record(record) if record.msg_info.is_none() => match group_uin {
+                Some(group_uin) => handle
+                    .upload_group_record(group_uin, record)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                MessageType::Friend(_) | MessageType::Temp => handle
-                    .upload_c2c_record(&chain.uid, record)
+                None => handle
+                    .upload_c2c_record(uid, record)
                     .await
                     .map_err(|e| BusinessError::GenericError(e.to_string()))?,
-                _ => {}
             },
+            Entity::Face(face) if face.sys_face_entry.is_none() => {
+                match handle
+                    .fetch_maybe_cached_sys_face(face.face_id as u32, false)
+                    .await
+                {
+                    Ok(Some(entry)) => {
+                        face.is_large_face |= entry.is_large_face();
+                        face.sys_face_entry = Some(entry);
+                    }
+                    Ok(None) => tracing::warn!("No sys face entry found for {}", face.face_id),
+                    Err(e) => tracing::error!("Failed to fetch sys faces: {:?}", e),
+                }
+            }
+            Entity::Mention(mention)
+                if mention.uin != 0 && (mention.uid.is_empty() || mention.name.is_none()) =>
+            {
+                resolve_mention(mention, group_uin, &handle).await
+            }
+            Entity::MultiMsg(multi) if multi.res_id.is_empty() => {
+                let (res_id, chains) = handle
+                    .multi_msg_upload(group_uin, std::mem::take(&mut multi.chains))
+                    .await
+                    .map_err(|e| BusinessError::GenericError(e.to_string()))?;
+                multi.res_id = res_id;
+                multi.chains = chains;
+            }
             _ => {}
         }
     }
     Ok(())
 }
 
+/// Fill the uid and display name of a mention built from an uin only
+async fn resolve_mention(
+    mention: &mut MentionEntity,
+    group_uin: Option<u32>,
+    handle: &Arc<BusinessHandle>,
+) {
+    let uin = mention.uin;
+    let found = match group_uin {
+        Some(group_uin) => handle
+            .fetch_maybe_cached_group_members(
+                group_uin,
+                |mm| {
+                    mm.get(&group_uin)
+                        .and_then(|members| members.iter().find(|m| m.uin == uin).cloned())
+                        .into_iter()
+                        .collect()
+                },
+                false,
+            )
+            .await
+            .map(|members| {
+                members.into_iter().next().map(|member| {
+                    let name = member
+                        .member_card
+                        .filter(|card| !card.is_empty())
+                        .or(member.member_name);
+                    (member.uid, name)
+                })
+            }),
+        None => handle
+            .fetch_maybe_cached_friends(
+                Some(uin),
+                |fm| {
+                    fm.get(&uin)
+                        .map(|f| f.value().clone())
+                        .into_iter()
+                        .collect()
+                },
+                false,
+            )
+            .await
+            .map(|friends| {
+                friends
+                    .into_iter()
+                    .next()
+                    .map(|friend| (friend.uid, Some(friend.nickname)))
+            }),
+    };
+    let (uid, name) = match found {
+        Ok(Some(found)) => found,
+        Ok(None) => {
+            tracing::warn!("Mentioned uin {} not found in cache", uin);
+            (String::new(), None)
+        }
+        Err(e) => {
+            tracing::error!("Failed to resolve mentioned uin {}: {:?}", uin, e);
+            (String::new(), None)
+        }
+    };
+    if mention.uid.is_empty() {
+        mention.uid = if uid.is_empty() {
+            handle.uin2uid_fast(uin, group_uin).await
+        } else {
+            uid
+        };
+    }
+    if mention.name.is_none() {
+        mention.name = Some(format!("@{}", name.unwrap_or_else(|| uin.to_string())));
+    }
+}
+
+/// Report the chat of `chain` as read up to it, without holding up the incoming logic
+fn mark_chain_read(chain: &MessageChain, handle: &Arc<BusinessHandle>) {
+    let handle = handle.clone();
+    let (group_uin, uid, sequence, time) = match &chain.typ {
+        MessageType::Group(grp) => (Some(grp.group_uin), String::new(), chain.sequence, 0),
+        MessageType::Friend(_) | MessageType::Temp(_) => {
+            (None, chain.uid.clone(), 0, chain.time.timestamp() as u32)
+        }
+        MessageType::None => return,
+    };
+    tokio::spawn(async move {
+        let res = match group_uin {
+            Some(group_uin) => handle.mark_group_read(group_uin, sequence).await,
+            None => handle.mark_friend_read(&uid, time).await,
+        };
+        if let Err(e) = res {
+            tracing::warn!("Failed to mark messages as read: {:?}", e);
+        }
+    });
+}
+
 // TODO: return result!!!
 async fn resolve_chain_metadata(
     chain: &mut MessageChain,
@@ -976,6 +1215,53 @@ async fn resolve_chain_metadata(
             }
             chain
         }
+        MessageType::Temp(ref temp) => {
+            if chain.uid.is_empty() {
+                chain.uid = handle
+                    .uin2uid_fast(chain.friend_uin, Some(temp.group_uin))
+                    .await;
+            }
+            chain
+        }
         _ => chain,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ClientConfig;
+    use crate::message::entity::text::TextEntity;
+
+    #[tokio::test]
+    async fn test_oversized_chain_packed() {
+        let text_chain = |len: usize| {
+            let mut chain = MessageChain::friend(10000, "u_test", "u_self");
+            chain.entities.push(Entity::Text(TextEntity {
+                text: "a".repeat(len),
+            }));
+            chain
+        };
+        // the heads carry random numbers whose varints vary by a byte or two between builds,
+        // so the chains are kept a little off the limit
+        const MARGIN: usize = 8;
+        let size =
+            MessagePacker::build(&text_chain(1000), &Context::for_test(Default::default())).len();
+        let ctx = Context::for_test(ClientConfig {
+            max_message_size: size,
+            ..Default::default()
+        });
+        let packed = async |mut chain: MessageChain| {
+            let mut called = false;
+            pack_oversized_chain(&mut chain, &ctx, async |_| {
+                called = true;
+                Ok(MessageChain::friend(10000, "u_test", "u_self"))
+            })
+            .await
+            .unwrap();
+            (called, chain.entities.is_empty())
+        };
+        assert_eq!(packed(text_chain(1000 - MARGIN)).await, (false, false));
+        assert_eq!(packed(text_chain(1000 + MARGIN)).await, (true, true));
+    }
+}
@@ -1,5 +1,8 @@
 use crate::entity::bot_friend::BotFriend;
+use crate::entity::bot_group::BotGroup;
 use crate::entity::bot_group_member::BotGroupMember;
+use crate::entity::bot_user_info::BotUserInfo;
+use crate::entity::sys_face::SysFaceEntry;
 use dashmap::DashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,7 +17,14 @@ pub struct Cache {
     pub(crate) uin2uid: Option<DashMap<u32, String>>,
     pub(crate) uid2uin: Option<DashMap<String, u32>>,
     pub(crate) cached_friends: Option<DashMap<u32, BotFriend>>,
+    pub(crate) cached_friend_categories: Option<DashMap<u32, String>>,
+    pub(crate) cached_groups: Option<DashMap<u32, BotGroup>>,
     pub(crate) cached_group_members: Option<DashMap<u32, Vec<BotGroupMember>>>,
+    pub(crate) cached_sys_faces: Option<DashMap<u32, SysFaceEntry>>,
+    pub(crate) cached_user_info: Option<DashMap<u32, BotUserInfo>>,
+    /// p_skey of each web domain, kept regardless of cache mode and dropped when a web API
+    /// call with it fails
+    pub(crate) web_cookies: DashMap<String, String>,
 }
 
 impl Cache {
@@ -32,7 +42,12 @@ impl Cache {
             uin2uid: Some(DashMap::new()),
             uid2uin: Some(DashMap::new()),
             cached_friends: Some(DashMap::new()),
+            cached_friend_categories: Some(DashMap::new()),
+            cached_groups: Some(DashMap::new()),
             cached_group_members: Some(DashMap::new()),
+            cached_sys_faces: Some(DashMap::new()),
+            cached_user_info: Some(DashMap::new()),
+            web_cookies: DashMap::new(),
         }
     }
 
@@ -42,7 +57,12 @@ impl Cache {
             uin2uid: None,
             uid2uin: None,
             cached_friends: Some(DashMap::new()),
+            cached_friend_categories: Some(DashMap::new()),
+            cached_groups: Some(DashMap::new()),
             cached_group_members: Some(DashMap::new()),
+            cached_sys_faces: Some(DashMap::new()),
+            cached_user_info: Some(DashMap::new()),
+            web_cookies: DashMap::new(),
         }
     }
 
@@ -52,7 +72,12 @@ impl Cache {
             uin2uid: None,
             uid2uin: None,
             cached_friends: None,
+            cached_friend_categories: None,
+            cached_groups: None,
             cached_group_members: None,
+            cached_sys_faces: None,
+            cached_user_info: None,
+            web_cookies: DashMap::new(),
         }
     }
 
@@ -64,4 +89,15 @@ impl Cache {
             .as_ref()
             .map(|uid2uin| uid2uin.insert(uid, uin));
     }
+
+    /// Apply `patch` to the cached group, if it is cached
+    pub(crate) fn patch_group(&self, group_uin: u32, patch: impl FnOnce(&mut BotGroup)) {
+        if let Some(mut group) = self
+            .cached_groups
+            .as_ref()
+            .and_then(|groups| groups.get_mut(&group_uin))
+        {
+            patch(&mut group);
+        }
+    }
 }
@@ -26,6 +26,22 @@ pub struct Context {
     pub(crate) config: Arc<ClientConfig>,
 }
 
+#[cfg(test)]
+impl Context {
+    /// An offline context for tests, with empty keys and no sign server
+    pub(crate) fn for_test(config: ClientConfig) -> Self {
+        Self {
+            app_info: AppInfo::get(config.protocol),
+            device: DeviceInfo::default(),
+            key_store: KeyStore::default(),
+            sign_provider: crate::core::sign::default_sign_provider(config.protocol, None),
+            crypto: Crypto::default(),
+            session: Session::new(),
+            config: Arc::new(config),
+        }
+    }
+}
+
 pub struct Crypto {
     pub login_p256: P256,
 }
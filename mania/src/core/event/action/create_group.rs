@@ -0,0 +1,43 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{
+    OidbSvcTrpcTcp0x7581, OidbSvcTrpcTcp0x7581createInfo, OidbSvcTrpcTcp0x7581response,
+    OidbSvcTrpcTcp0x7581uid,
+};
+
+/// Create a group named `name` with `member_uids`, or invite `member_uids` into `group_uin`
+/// if it is not 0
+#[oidb_command(0x758, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct CreateGroupEvent {
+    pub group_uin: u32,
+    pub name: String,
+    pub member_uids: Vec<String>,
+}
+
+impl ClientEvent for CreateGroupEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x7581 {
+            group_uin: self.group_uin,
+            uid_list: self
+                .member_uids
+                .iter()
+                .map(|uid| OidbSvcTrpcTcp0x7581uid {
+                    invite_uid: uid.clone(),
+                })
+                .collect(),
+            create_info: (self.group_uin == 0).then(|| OidbSvcTrpcTcp0x7581createInfo {
+                group_name: self.name.clone(),
+                group_type: 1,
+            }),
+            field10: 0,
+        };
+        Ok(OidbPacket::new(0x758, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0x7581response>(packet)?;
+        Ok(ClientResult::single(Box::new(dda!(Self {
+            group_uin: response.group_uin,
+        }))))
+    }
+}
@@ -0,0 +1,101 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0xFd6, OidbSvcTrpcTcp0xFd6Response};
+
+fn build_category(sub_command: u32, request: OidbSvcTrpcTcp0xFd6) -> CEBuildResult {
+    Ok(OidbPacket::new(0xfd6, sub_command, request.encode_to_vec(), false, false).to_binary())
+}
+
+#[oidb_command(0xfd6, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct CreateFriendCategoryEvent {
+    pub name: String,
+    pub category_id: u32,
+}
+
+impl ClientEvent for CreateFriendCategoryEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_category(
+            1,
+            dda!(OidbSvcTrpcTcp0xFd6 {
+                category_name: self.name.clone(),
+            }),
+        )
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0xFd6Response>(packet)?;
+        Ok(ClientResult::single(Box::new(dda!(Self {
+            category_id: response.category_id,
+        }))))
+    }
+}
+
+#[oidb_command(0xfd6, 2)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct RenameFriendCategoryEvent {
+    pub category_id: u32,
+    pub name: String,
+}
+
+impl ClientEvent for RenameFriendCategoryEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_category(
+            2,
+            dda!(OidbSvcTrpcTcp0xFd6 {
+                category_id: self.category_id,
+                category_name: self.name.clone(),
+            }),
+        )
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
+
+#[oidb_command(0xfd6, 3)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct DeleteFriendCategoryEvent {
+    pub category_id: u32,
+}
+
+impl ClientEvent for DeleteFriendCategoryEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_category(
+            3,
+            dda!(OidbSvcTrpcTcp0xFd6 {
+                category_id: self.category_id,
+            }),
+        )
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
+
+#[oidb_command(0xfd6, 4)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct MoveFriendCategoryEvent {
+    pub friend_uid: String,
+    pub category_id: u32,
+}
+
+impl ClientEvent for MoveFriendCategoryEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_category(
+            4,
+            dda!(OidbSvcTrpcTcp0xFd6 {
+                category_id: self.category_id,
+                friend_uid: Some(self.friend_uid.clone()),
+            }),
+        )
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
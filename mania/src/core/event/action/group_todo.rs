@@ -0,0 +1,63 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0xF8e1, OidbSvcTrpcTcp0xF90};
+
+fn build_todo(sub_command: u32, group_uin: u32) -> CEBuildResult {
+    let request = OidbSvcTrpcTcp0xF90 { group_uin };
+    Ok(OidbPacket::new(0xf90, sub_command, request.encode_to_vec(), false, false).to_binary())
+}
+
+#[oidb_command(0xf8e, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetGroupTodoEvent {
+    pub group_uin: u32,
+    pub sequence: u32,
+}
+
+impl ClientEvent for SetGroupTodoEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0xF8e1 {
+            group_uin: self.group_uin,
+            sequence: self.sequence,
+        };
+        Ok(OidbPacket::new(0xf8e, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
+
+#[oidb_command(0xf90, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct RemoveGroupTodoEvent {
+    pub group_uin: u32,
+}
+
+impl ClientEvent for RemoveGroupTodoEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_todo(1, self.group_uin)
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
+
+#[oidb_command(0xf90, 2)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct CompleteGroupTodoEvent {
+    pub group_uin: u32,
+}
+
+impl ClientEvent for CompleteGroupTodoEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        build_todo(2, self.group_uin)
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -1 +1,10 @@
-
+pub mod create_group;
+pub mod friend_category;
+pub mod group_todo;
+pub mod rename_group;
+pub mod request_friend;
+pub mod request_join_group;
+pub mod set_friend_remark;
+pub mod set_group_remark;
+pub mod set_pinned;
+pub mod set_status;
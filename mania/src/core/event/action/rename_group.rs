@@ -0,0 +1,26 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0x89A15, OidbSvcTrpcTcp0x89A15body};
+
+#[oidb_command(0x89a, 15)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct RenameGroupEvent {
+    pub group_uin: u32,
+    pub name: String,
+}
+
+impl ClientEvent for RenameGroupEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x89A15 {
+            group_uin: self.group_uin,
+            body: Some(OidbSvcTrpcTcp0x89A15body {
+                target_name: self.name.clone(),
+            }),
+        };
+        Ok(OidbPacket::new(0x89a, 15, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
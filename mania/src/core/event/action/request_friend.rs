@@ -0,0 +1,29 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::OidbSvcTrpcTcp0x7C25;
+
+#[oidb_command(0x7c2, 5)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct RequestFriendEvent {
+    pub target_uin: u32,
+    pub message: String,
+    pub answer: Option<String>,
+}
+
+impl ClientEvent for RequestFriendEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x7C25 {
+            target_uin: self.target_uin,
+            source_id: 3001,
+            sub_source_id: 1,
+            message: self.message.clone(),
+            answer: self.answer.clone(),
+            category_id: 0,
+        };
+        Ok(OidbPacket::new(0x7c2, 5, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -0,0 +1,26 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::OidbSvcTrpcTcp0x8A17;
+
+#[oidb_command(0x8a1, 7)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct RequestJoinGroupEvent {
+    pub group_uin: u32,
+    pub message: String,
+}
+
+impl ClientEvent for RequestJoinGroupEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x8A17 {
+            group_uin: self.group_uin,
+            field2: 0,
+            message: self.message.clone(),
+            source_id: 30001,
+        };
+        Ok(OidbPacket::new(0x8a1, 7, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -0,0 +1,24 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::OidbSvcTrpcTcp0xB6e2;
+
+#[oidb_command(0xb6e, 2)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetFriendRemarkEvent {
+    pub friend_uid: String,
+    pub remark: String,
+}
+
+impl ClientEvent for SetFriendRemarkEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0xB6e2 {
+            target_uid: self.friend_uid.clone(),
+            remark: self.remark.clone(),
+        };
+        Ok(OidbPacket::new(0xb6e, 2, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
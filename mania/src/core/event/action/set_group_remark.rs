@@ -0,0 +1,26 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0xF161, OidbSvcTrpcTcp0xF161body};
+
+#[oidb_command(0xf16, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetGroupRemarkEvent {
+    pub group_uin: u32,
+    pub remark: String,
+}
+
+impl ClientEvent for SetGroupRemarkEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0xF161 {
+            body: Some(OidbSvcTrpcTcp0xF161body {
+                group_uin: self.group_uin,
+                target_remark: self.remark.clone(),
+            }),
+        };
+        Ok(OidbPacket::new(0xf16, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -0,0 +1,45 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{
+    OidbSvcTrpcTcp0x5D618, OidbSvcTrpcTcp0x5D618field400, OidbSvcTrpcTcp0x5D618info,
+};
+
+/// Pin or unpin a friend chat (`friend_uid`) or a group chat (`group_uin`)
+#[oidb_command(0x5d6, 18)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetPinnedEvent {
+    pub friend_uid: Option<String>,
+    pub group_uin: Option<u32>,
+    pub is_pin: bool,
+}
+
+impl ClientEvent for SetPinnedEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let timestamp = match self.is_pin {
+            true => (chrono::Utc::now().timestamp() as u32)
+                .to_be_bytes()
+                .to_vec(),
+            false => Vec::new(),
+        };
+        let request = OidbSvcTrpcTcp0x5D618 {
+            field1: 0,
+            info: Some(OidbSvcTrpcTcp0x5D618info {
+                friend_uid: self.friend_uid.clone(),
+                group_uin: self.group_uin,
+                field400: Some(OidbSvcTrpcTcp0x5D618field400 {
+                    field1: 13578,
+                    timestamp,
+                }),
+            }),
+            field3: match self.group_uin {
+                Some(_) => 12,
+                None => 1,
+            },
+        };
+        Ok(OidbPacket::new(0x5d6, 18, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::action::{SetStatus, SetStatusCustomExt, SetStatusResponse};
+use crate::entity::online_status::CustomStatus;
+
+#[command("trpc.qq_new_tech.status_svc.StatusService.SetStatus")]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetStatusEvent {
+    pub status: u32,
+    pub ext_status: u32,
+    pub battery_status: u32,
+    pub custom: Option<CustomStatus>,
+    /// Non-empty if the server rejected the status
+    pub message: String,
+}
+
+impl ClientEvent for SetStatusEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = SetStatus {
+            status: self.status,
+            ext_status: self.ext_status,
+            battery_status: self.battery_status,
+            custom_ext: self.custom.as_ref().map(|custom| SetStatusCustomExt {
+                face_id: custom.face_id,
+                text: Some(custom.wording.clone()),
+                field3: 1,
+            }),
+        };
+        Ok(BinaryPacket(request.encode_to_vec().into()))
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = SetStatusResponse::decode(packet)?;
+        Ok(ClientResult::single(Box::new(dda!(Self {
+            message: response.message,
+        }))))
+    }
+}
@@ -0,0 +1,39 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::message::{SsoReaddedReport, SsoReaddedReportC2c, SsoReaddedReportGroup};
+
+#[command("trpc.msg.msg_svc.MsgService.SsoReadedReport")]
+#[derive(Debug, ServerEvent, Default)]
+pub struct MarkReadEvent {
+    /// Report a group chat when present, otherwise the c2c chat of `target_uid`
+    pub group_uin: Option<u32>,
+    pub target_uid: Option<String>,
+    pub start_sequence: u32,
+    pub time: u32,
+}
+
+impl ClientEvent for MarkReadEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = match self.group_uin {
+            Some(group_uin) => SsoReaddedReport {
+                group: Some(SsoReaddedReportGroup {
+                    group_uin,
+                    start_sequence: self.start_sequence,
+                }),
+                c2c: None,
+            },
+            None => SsoReaddedReport {
+                group: None,
+                c2c: Some(SsoReaddedReportC2c {
+                    target_uid: self.target_uid.clone(),
+                    time: self.time,
+                    start_sequence: self.start_sequence,
+                }),
+            },
+        };
+        Ok(BinaryPacket(request.encode_to_vec().into()))
+    }
+
+    fn parse(_: Bytes, _: &Context) -> CEParseResult {
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
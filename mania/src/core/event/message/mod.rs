@@ -4,7 +4,9 @@ pub mod image_c2c_download;
 pub mod image_c2c_upload;
 pub mod image_group_download;
 pub mod image_group_upload;
+pub mod mark_read;
 pub mod multi_msg_download;
+pub mod multi_msg_upload;
 pub mod push_msg;
 pub mod record_c2c_download;
 pub mod record_c2c_upload;
@@ -1,6 +1,7 @@
 use crate::core::event::prelude::*;
 use crate::core::protos::message::{
-    LongMsgResult, LongMsgSettings, LongMsgUid, RecvLongMsgInfo, RecvLongMsgReq, RecvLongMsgResp,
+    LongMsgResult, LongMsgSettings, LongMsgUid, RawLongMsgResult, RecvLongMsgInfo, RecvLongMsgReq,
+    RecvLongMsgResp,
 };
 use crate::message::chain::MessageChain;
 use crate::message::packer::MessagePacker;
@@ -44,18 +45,31 @@ impl ClientEvent for MultiMsgDownloadEvent {
         let inflate = gzip::decompress(&inflate).ok_or_else(|| {
             EventError::OtherError("Failed to decompress long message".to_string())
         })?;
-        let result = LongMsgResult::decode(Bytes::from(inflate))?;
+        let inflate = Bytes::from(inflate);
+        let result = LongMsgResult::decode(inflate.clone())?;
         let main = result
             .action
             .into_iter()
             .find(|a| a.action_command == "MultiMsg")
             .ok_or_else(|| EventError::OtherError("Failed to find MultiMsg command".to_string()))?;
+        // the same payload with the elements left encoded, see `Entity::from_raw_elems`
+        let mut raw_bodies = RawLongMsgResult::decode(inflate)?
+            .action
+            .into_iter()
+            .find(|a| a.action_command == "MultiMsg")
+            .and_then(|a| a.action_data)
+            .map(|data| data.msg_body)
+            .unwrap_or_default()
+            .into_iter();
         let chains = main
             .action_data
             .ok_or_else(|| EventError::OtherError("Failed to find action_data".to_string()))?
             .msg_body
             .into_iter()
-            .map(|body| MessagePacker::parse_fake_chain(body, ctx))
+            .map(|body| {
+                let raw_elems = MessagePacker::raw_elems(raw_bodies.next());
+                MessagePacker::parse_fake_chain(body, raw_elems, ctx)
+            })
             .collect::<Result<Vec<MessageChain>, String>>()
             .map_err(EventError::OtherError)?;
         Ok(ClientResult::single(Box::new(dda!(
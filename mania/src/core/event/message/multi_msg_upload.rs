@@ -0,0 +1,72 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::message::{
+    LongMsgSettings, LongMsgUid, RawLongMsgAction, RawLongMsgContent, RawLongMsgResult,
+    SendLongMsgInfo, SendLongMsgReq, SendLongMsgResp,
+};
+use crate::message::chain::MessageChain;
+use crate::message::packer::MessagePacker;
+use crate::utility::compress::gzip;
+use mania_macros::{ServerEvent, command};
+
+#[command("trpc.group.long_msg_interface.MsgService.SsoSendLongMsg")]
+#[derive(Debug, ServerEvent, Default)]
+pub struct MultiMsgUploadEvent {
+    pub group_uin: Option<u32>,
+    pub chains: Vec<MessageChain>,
+    pub res_id: Option<String>,
+}
+
+impl ClientEvent for MultiMsgUploadEvent {
+    fn build(&self, ctx: &Context) -> CEBuildResult {
+        let result = RawLongMsgResult {
+            action: vec![RawLongMsgAction {
+                action_command: "MultiMsg".to_string(),
+                action_data: Some(RawLongMsgContent {
+                    msg_body: self
+                        .chains
+                        .iter()
+                        .map(|chain| MessagePacker::build_fake(chain, ctx))
+                        .collect(),
+                }),
+            }],
+        };
+        let payload = gzip::compress(&result.encode_to_vec());
+        let packet = SendLongMsgReq {
+            info: Some(SendLongMsgInfo {
+                r#type: if self.group_uin.is_some() { 3 } else { 1 },
+                uid: Some(LongMsgUid {
+                    uid: Some(match self.group_uin {
+                        Some(group_uin) => group_uin.to_string(),
+                        None => ctx
+                            .key_store
+                            .uid
+                            .load()
+                            .as_deref()
+                            .cloned()
+                            .unwrap_or_default(),
+                    }),
+                }),
+                group_uin: self.group_uin,
+                payload: Some(payload),
+            }),
+            settings: Some(LongMsgSettings {
+                field1: 4,
+                field2: 1,
+                field3: 7,
+                field4: 0,
+            }),
+        };
+        Ok(BinaryPacket(packet.encode_to_vec().into()))
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let packet = SendLongMsgResp::decode(packet)?;
+        let res_id = packet
+            .result
+            .ok_or_else(|| EventError::OtherError("Missing SendLongMsgResult".to_string()))?
+            .res_id;
+        Ok(ClientResult::single(Box::new(dda!(MultiMsgUploadEvent {
+            res_id: Some(res_id),
+        }))))
+    }
+}
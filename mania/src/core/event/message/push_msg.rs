@@ -10,6 +10,7 @@ use crate::core::event::notify::friend_sys_request::FriendSysRequestEvent;
 use crate::core::event::notify::group_sys_admin::GroupSysAdminEvent;
 use crate::core::event::notify::group_sys_decrease::GroupSysDecreaseEvent;
 use crate::core::event::notify::group_sys_essence::GroupSysEssenceEvent;
+use crate::core::event::notify::group_sys_grey_tip::GroupSysGreyTipEvent;
 use crate::core::event::notify::group_sys_increase::GroupSysIncreaseEvent;
 use crate::core::event::notify::group_sys_invite::GroupSysInviteEvent;
 use crate::core::event::notify::group_sys_member_enter::GroupSysMemberEnterEvent;
@@ -28,9 +29,10 @@ use crate::core::event::prelude::*;
 use crate::core::protos::message::{
     Event0x210Sub39Notify, FriendRecall, FriendRequest, GeneralGrayTipInfo, GroupAdmin,
     GroupChange, GroupInvitation, GroupInvite, GroupJoin, GroupMemberEnterNotify, GroupMute,
-    GroupNameChange, NewFriend, NotifyMessageBody, OperatorInfo, PushMsg, SelfRenameNotify,
-    SpecialTittleNotify,
+    GroupNameChange, NewFriend, NotifyMessageBody, OperatorInfo, PushMsg, RawPushMsg,
+    SelfRenameNotify, SpecialTittleNotify,
 };
+use crate::entity::grey_tip::GreyTip;
 use crate::message::chain::MessageChain;
 use crate::message::packer::MessagePacker;
 use regex::Regex;
@@ -131,7 +133,7 @@ impl ClientEvent for PushMessageEvent {
     }
 
     fn parse(bytes: Bytes, ctx: &Context) -> CEParseResult {
-        let mut packet = PushMsg::decode(bytes)?;
+        let mut packet = PushMsg::decode(bytes.clone())?;
         let typ = packet
             .message
             .as_ref()
@@ -154,11 +156,14 @@ impl ClientEvent for PushMessageEvent {
             | PkgType::GroupMessage
             | PkgType::TempMessage
             | PkgType::PrivateRecordMessage => {
+                // the same packet with the elements left encoded, see `Entity::from_raw_elems`
+                let raw_elems = MessagePacker::raw_elems(RawPushMsg::decode(bytes)?.message);
                 chain = Some(
                     MessagePacker::parse_chain(
                         packet.message.ok_or_else(|| {
                             EventError::OtherError("PushMsgBody is None".to_string())
                         })?,
+                        raw_elems,
                         ctx,
                     )
                     .map_err(|e| EventError::OtherError(format!("parse_chain failed: {}", e)))?,
@@ -597,10 +602,26 @@ fn process_event_0x2dc<'a>(
                 extract_unstable_msg_content(packet, "0x2dc sub type 20 missing msg_content")?;
             let (group_uin, mut grey_tip) =
                 extract_0x2dc_fucking_head::<NotifyMessageBody>(msg_content)?;
-            let gray_tip_info = match grey_tip.gray_tip_info.as_mut() {
-                Some(info) if info.busi_type == 12 => info,
-                _ => return Ok(extra),
+            let Some(gray_tip_info) = grey_tip.gray_tip_info.as_mut() else {
+                return Ok(extra);
             };
+            extra.as_mut().unwrap().push(Box::new(GroupSysGreyTipEvent {
+                group_uin,
+                grey_tip: GreyTip {
+                    busi_type: gray_tip_info.busi_type,
+                    busi_id: gray_tip_info.busi_id,
+                    template_id: gray_tip_info.templ_id,
+                    content: gray_tip_info.content.clone(),
+                    params: gray_tip_info
+                        .msg_templ_param
+                        .iter()
+                        .map(|param| (param.key.clone(), param.value.clone()))
+                        .collect(),
+                },
+            }));
+            if gray_tip_info.busi_type != 12 {
+                return Ok(extra);
+            }
             let poke_args = extract_poke_info(gray_tip_info);
             extra.as_mut().unwrap().push(Box::new(GroupSysPokeEvent {
                 group_uin,
@@ -21,8 +21,7 @@ pub struct MessageResult {
 
 impl ClientEvent for SendMessageEvent {
     fn build(&self, ctx: &Context) -> CEBuildResult {
-        let packet = MessagePacker::build(&self.chain, ctx);
-        Ok(BinaryPacket(packet.encode_to_vec().into()))
+        Ok(BinaryPacket(MessagePacker::build(&self.chain, ctx).into()))
     }
 
     fn parse(bytes: Bytes, _: &Context) -> CEParseResult {
@@ -0,0 +1,8 @@
+use crate::core::event::prelude::*;
+use crate::entity::grey_tip::GreyTip;
+
+#[derive(Debug, DummyEvent, Default)]
+pub struct GroupSysGreyTipEvent {
+    pub group_uin: u32,
+    pub grey_tip: GreyTip,
+}
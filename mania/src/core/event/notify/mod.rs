@@ -7,6 +7,7 @@ pub mod friend_sys_request;
 pub mod group_sys_admin;
 pub mod group_sys_decrease;
 pub mod group_sys_essence;
+pub mod group_sys_grey_tip;
 pub mod group_sys_increase;
 pub mod group_sys_invite;
 pub mod group_sys_member_enter;
@@ -0,0 +1,38 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::action::{FaceRoamRequest, FaceRoamResponse, PlatInfo};
+
+#[command("Faceroam.OpReq")]
+#[derive(Debug, ServerEvent, Default)]
+pub struct FaceRoamEvent {
+    pub ret_code: u32,
+    pub err_msg: String,
+    pub file_names: Vec<String>,
+    pub bid: String,
+}
+
+impl ClientEvent for FaceRoamEvent {
+    fn build(&self, ctx: &Context) -> CEBuildResult {
+        let request = FaceRoamRequest {
+            comm: Some(PlatInfo {
+                im_plat: 1,
+                os_version: Some(String::new()),
+                q_version: Some(ctx.app_info.current_version.to_string()),
+            }),
+            self_uin: **ctx.key_store.uin.load(),
+            sub_cmd: 1,
+            field6: 1,
+        };
+        Ok(BinaryPacket(request.encode_to_vec().into()))
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = FaceRoamResponse::decode(packet)?;
+        let user_info = response.user_info.unwrap_or_default();
+        Ok(ClientResult::single(Box::new(Self {
+            ret_code: response.ret_code,
+            err_msg: response.err_msg,
+            file_names: user_info.file_name,
+            bid: user_info.bid,
+        })))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0x102A0, OidbSvcTrpcTcp0x102A0response};
+
+#[oidb_command(0x102a, 0)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct FetchCookieEvent {
+    pub domains: Vec<String>,
+    /// Domain to p_skey
+    pub cookies: HashMap<String, String>,
+}
+
+impl ClientEvent for FetchCookieEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x102A0 {
+            domain: self.domains.clone(),
+        };
+        Ok(OidbPacket::new(0x102a, 0, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0x102A0response>(packet)?;
+        let cookies = response
+            .urls
+            .into_iter()
+            .map(|url| (url.key, String::from_utf8_lossy(&url.value).into_owned()))
+            .collect();
+        Ok(ClientResult::single(Box::new(dda!(Self { cookies }))))
+    }
+}
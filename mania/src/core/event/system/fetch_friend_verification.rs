@@ -0,0 +1,31 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0x7C11, OidbSvcTrpcTcp0x7C11response};
+use crate::entity::join_verification::JoinVerification;
+
+#[oidb_command(0x7c1, 1)]
+#[derive(Debug, ServerEvent)]
+pub struct FetchFriendVerificationEvent {
+    pub target_uin: u32,
+    pub verification: JoinVerification,
+}
+
+impl ClientEvent for FetchFriendVerificationEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x7C11 {
+            target_uin: self.target_uin,
+            field2: 1,
+        };
+        Ok(OidbPacket::new(0x7c1, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0x7C11response>(packet)?;
+        Ok(ClientResult::single(Box::new(Self {
+            target_uin: response.target_uin,
+            verification: JoinVerification::from_friend_setting(
+                response.add_setting,
+                response.question.unwrap_or_default(),
+            ),
+        })))
+    }
+}
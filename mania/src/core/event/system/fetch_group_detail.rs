@@ -0,0 +1,54 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{
+    D88dGroupInfo, OidbSvcTrpcTcp0x88D, OidbSvcTrpcTcp0x88DConfig2, OidbSvcTrpcTcp0x88DResponse,
+};
+use crate::entity::join_verification::JoinVerification;
+
+#[oidb_command(0x88d, 0)]
+#[derive(Debug, ServerEvent)]
+pub struct FetchGroupDetailEvent {
+    pub group_uin: u32,
+    pub group_name: String,
+    pub member_count: u32,
+    pub max_member: u32,
+    pub verification: JoinVerification,
+}
+
+impl ClientEvent for FetchGroupDetailEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x88D {
+            app_id: 537099973,
+            config2: Some(OidbSvcTrpcTcp0x88DConfig2 {
+                group_uin: self.group_uin,
+                group_info: Some(dda!(D88dGroupInfo {
+                    group_owner: Some(true),
+                    group_member_max_num: Some(true),
+                    group_member_num: Some(true),
+                    group_option: Some(true),
+                    group_name: Some(String::new()),
+                    group_uin: Some(true),
+                    group_question: Some(String::new()),
+                })),
+            }),
+        };
+        Ok(OidbPacket::new(0x88d, 0, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0x88DResponse>(packet)?;
+        let info = response
+            .info
+            .and_then(|info| info.group_info)
+            .ok_or_else(|| EventError::OtherError("Missing group info in response".into()))?;
+        Ok(ClientResult::single(Box::new(Self {
+            group_uin: info.group_uin,
+            group_name: info.group_name,
+            member_count: info.group_member_num,
+            max_member: info.group_member_max_num,
+            verification: JoinVerification::from_group_option(
+                info.group_option,
+                info.group_question,
+            ),
+        })))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{
+    OidbSvcTrpcTcp0xFe52, OidbSvcTrpcTcp0xFe52config, OidbSvcTrpcTcp0xFe52config1,
+    OidbSvcTrpcTcp0xFe52config2, OidbSvcTrpcTcp0xFe52config3, OidbSvcTrpcTcp0xFe52response,
+};
+use crate::entity::bot_group::BotGroup;
+use chrono::DateTime;
+
+#[oidb_command(0xfe5, 2)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct FetchGroupsEvent {
+    pub groups: Vec<BotGroup>,
+}
+
+impl ClientEvent for FetchGroupsEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0xFe52 {
+            config: Some(OidbSvcTrpcTcp0xFe52config {
+                config1: Some(OidbSvcTrpcTcp0xFe52config1 {
+                    group_owner: true,
+                    field2: true,
+                    member_max: true,
+                    member_count: true,
+                    group_name: true,
+                    field8: true,
+                    field9: true,
+                    field10: true,
+                    field11: true,
+                    field12: true,
+                    field13: true,
+                    field14: true,
+                    field15: true,
+                    field16: true,
+                    field17: true,
+                    field18: true,
+                    question: true,
+                    field20: true,
+                    field22: true,
+                    field23: true,
+                    field24: true,
+                    field25: true,
+                    field26: true,
+                    field27: true,
+                    field28: true,
+                    field29: true,
+                    field30: true,
+                    field31: true,
+                    field32: true,
+                    field5001: true,
+                    field5002: true,
+                    field5003: true,
+                }),
+                config2: Some(OidbSvcTrpcTcp0xFe52config2 {
+                    field1: true,
+                    field2: true,
+                    field3: true,
+                    field4: true,
+                    field5: true,
+                    field6: true,
+                    field7: true,
+                    field8: true,
+                }),
+                config3: Some(OidbSvcTrpcTcp0xFe52config3 {
+                    field5: true,
+                    field6: true,
+                }),
+            }),
+        };
+        Ok(OidbPacket::new(0xfe5, 2, request.encode_to_vec(), false, true).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0xFe52response>(packet)?;
+        let groups = response
+            .groups
+            .into_iter()
+            .map(|group| {
+                let info = group.info.unwrap_or_default();
+                BotGroup::new(
+                    group.group_uin,
+                    info.group_name,
+                    info.group_owner.map(|owner| owner.uid).unwrap_or_default(),
+                    info.member_count,
+                    info.member_max,
+                    DateTime::from_timestamp(info.create_time_stamp as i64, 0).unwrap_or_default(),
+                    group.ext_info.map(|ext| ext.group_memo).unwrap_or_default(),
+                )
+            })
+            .collect();
+        Ok(ClientResult::single(Box::new(Self { groups })))
+    }
+}
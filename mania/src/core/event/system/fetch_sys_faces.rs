@@ -0,0 +1,53 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{OidbSvcTrpcTcp0x91541, OidbSvcTrpcTcp0x91541response};
+use crate::entity::sys_face::{SysFaceEntry, SysFacePackEntry};
+
+#[oidb_command(0x9154, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct FetchSysFacesEvent {
+    pub face_packs: Vec<SysFacePackEntry>,
+}
+
+impl ClientEvent for FetchSysFacesEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let request = OidbSvcTrpcTcp0x91541 {
+            field1: 0,
+            field2: 7,
+            field3: "0".to_string(),
+        };
+        Ok(OidbPacket::new(0x9154, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        let response = OidbPacket::parse_into::<OidbSvcTrpcTcp0x91541response>(packet)?;
+        let face_packs = [response.common_face, response.special_big_face]
+            .into_iter()
+            .flatten()
+            .flat_map(|content| content.emoji_list)
+            .map(|pack| {
+                SysFacePackEntry::new(
+                    pack.emoji_pack_name,
+                    pack.emoji_detail
+                        .into_iter()
+                        .map(|detail| {
+                            SysFaceEntry::new(
+                                detail.q_sid,
+                                detail.q_des,
+                                detail.em_code,
+                                detail.q_cid,
+                                detail.ani_sticker_type,
+                                detail.ani_sticker_pack_id,
+                                detail.ani_sticker_id,
+                                detail.url.and_then(|url| url.base_url),
+                                Some(detail.emoji_name_alias),
+                                detail.ani_sticker_width,
+                                detail.ani_sticker_height,
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        Ok(ClientResult::single(Box::new(Self { face_packs })))
+    }
+}
@@ -1,11 +1,18 @@
 pub mod alive;
+pub mod face_roam;
+pub mod fetch_cookie;
 pub mod fetch_filtered_group_request;
 pub mod fetch_friend;
+pub mod fetch_friend_verification;
+pub mod fetch_group_detail;
 pub mod fetch_group_requests;
+pub mod fetch_groups;
 pub mod fetch_highway_ticket;
 pub mod fetch_members;
 pub mod fetch_rkey;
+pub mod fetch_sys_faces;
 pub mod fetch_user_info;
 pub mod info_sync;
 pub mod kick_nt;
 pub mod nt_sso_alive;
+pub mod set_profile;
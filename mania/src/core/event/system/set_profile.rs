@@ -0,0 +1,58 @@
+use crate::core::event::prelude::*;
+use crate::core::protos::service::oidb::{
+    OidbFriendByteProperty, OidbSvcTrpcTcp0x1121, OidbTwoNumber,
+};
+use crate::entity::bot_user_info::GenderInfo;
+use chrono::{Datelike, NaiveDate};
+
+#[oidb_command(0x112, 1)]
+#[derive(Debug, ServerEvent, Default)]
+pub struct SetProfileEvent {
+    pub uin: u32,
+    pub nickname: Option<String>,
+    pub signature: Option<String>,
+    pub gender: Option<GenderInfo>,
+    pub birthday: Option<NaiveDate>,
+}
+
+impl ClientEvent for SetProfileEvent {
+    fn build(&self, _: &Context) -> CEBuildResult {
+        let mut bytes_properties = Vec::new();
+        let mut number_properties = Vec::new();
+        if let Some(nickname) = &self.nickname {
+            bytes_properties.push(OidbFriendByteProperty {
+                code: 20002,
+                value: nickname.as_bytes().to_vec(),
+            });
+        }
+        if let Some(signature) = &self.signature {
+            bytes_properties.push(OidbFriendByteProperty {
+                code: 102,
+                value: signature.as_bytes().to_vec(),
+            });
+        }
+        if let Some(birthday) = &self.birthday {
+            let mut value = (birthday.year() as u16).to_be_bytes().to_vec();
+            value.push(birthday.month() as u8);
+            value.push(birthday.day() as u8);
+            bytes_properties.push(OidbFriendByteProperty { code: 20031, value });
+        }
+        if let Some(gender) = self.gender {
+            number_properties.push(OidbTwoNumber {
+                number1: 20009,
+                number2: gender as u32,
+            });
+        }
+        let request = OidbSvcTrpcTcp0x1121 {
+            uin: self.uin,
+            number_properties,
+            bytes_properties,
+        };
+        Ok(OidbPacket::new(0x112, 1, request.encode_to_vec(), false, false).to_binary())
+    }
+
+    fn parse(packet: Bytes, _: &Context) -> CEParseResult {
+        OidbPacket::parse(packet)?;
+        Ok(ClientResult::single(Box::new(Self::default())))
+    }
+}
@@ -1,26 +1,64 @@
 use reqwest::Client;
 use reqwest::Error;
 use reqwest::header::HeaderMap;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
 pub struct HttpClient {
     client: Client,
+    /// Replaces the scheme and host of every requested url, e.g. `http://127.0.0.1:8080` to
+    /// route all requests to a local mock server
+    origin_override: Option<String>,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl HttpClient {
     pub fn new() -> Self {
         HttpClient {
             client: Client::new(),
+            origin_override: None,
         }
     }
 
+    pub fn with_client(client: Client) -> Self {
+        HttpClient {
+            client,
+            origin_override: None,
+        }
+    }
+
+    pub fn with_origin_override(mut self, origin: impl Into<String>) -> Self {
+        self.origin_override = Some(origin.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    fn resolve_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        let Some(origin) = &self.origin_override else {
+            return Cow::Borrowed(url);
+        };
+        let path = url
+            .split_once("://")
+            .map(|(_, rest)| rest.find('/').map_or("", |i| &rest[i..]))
+            .unwrap_or(url);
+        Cow::Owned(format!("{}{}", origin, path))
+    }
+
     pub async fn get_async(
         &self,
         url: &str,
         params: Option<&HashMap<String, String>>,
+        headers: Option<HeaderMap>,
     ) -> Result<String, Error> {
-        let mut request = self.client.get(url);
+        let mut request = self
+            .client
+            .get(self.resolve_url(url).as_ref())
+            .headers(headers.unwrap_or_default());
         if let Some(query_params) = params {
             request = request.query(query_params);
         }
@@ -28,13 +66,35 @@ impl HttpClient {
         response.text().await
     }
 
+    pub async fn get_binary_async(
+        &self,
+        url: &str,
+        headers: Option<HeaderMap>,
+    ) -> Result<Vec<u8>, Error> {
+        let response = self
+            .client
+            .get(self.resolve_url(url).as_ref())
+            .headers(headers.unwrap_or_default())
+            .send()
+            .await?;
+        let bytes = response.bytes().await?;
+        Ok(bytes.to_vec())
+    }
+
     pub async fn post_form_async(
         &self,
         url: &str,
         payload: Option<HashMap<String, String>>,
+        headers: Option<HeaderMap>,
     ) -> Result<String, Error> {
         let form_data = payload.unwrap_or_default(); // FIXME:
-        let response = self.client.post(url).form(&form_data).send().await?;
+        let response = self
+            .client
+            .post(self.resolve_url(url).as_ref())
+            .headers(headers.unwrap_or_default())
+            .form(&form_data)
+            .send()
+            .await?;
         response.text().await
     }
 
@@ -47,7 +107,7 @@ impl HttpClient {
     ) -> Result<Vec<u8>, Error> {
         let response = self
             .client
-            .post(url)
+            .post(self.resolve_url(url).as_ref())
             .headers(headers.unwrap_or_default())
             .body(payload.to_vec())
             .send()
@@ -60,3 +120,38 @@ static ASYNC_HTTP_CLIENT: OnceLock<HttpClient> = OnceLock::new();
 pub fn client() -> &'static HttpClient {
     ASYNC_HTTP_CLIENT.get_or_init(HttpClient::new)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_origin_override() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let body = r#"{"ec":0}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+        let client = HttpClient::new().with_origin_override(format!("http://{}/", addr));
+        let res = client
+            .get_async("https://web.qun.qq.com/cgi-bin/test?a=1", None, None)
+            .await
+            .unwrap();
+        assert_eq!(res, r#"{"ec":0}"#);
+        let request = server.await.unwrap();
+        assert!(request.starts_with("GET /cgi-bin/test?a=1 HTTP/1.1"));
+    }
+}
@@ -2,9 +2,15 @@ use crate::core::business::BusinessHandle;
 use crate::core::cache::CacheMode;
 use crate::core::event::downcast_mut_major_event;
 use crate::core::event::system::fetch_friend::FetchFriendsEvent;
+use crate::core::event::system::fetch_groups::FetchGroupsEvent;
 use crate::core::event::system::fetch_members::FetchMembersEvent;
+use crate::core::event::system::fetch_sys_faces::FetchSysFacesEvent;
+use crate::core::event::system::fetch_user_info::FetchUserInfoEvent;
 use crate::entity::bot_friend::{BotFriend, BotFriendGroup};
+use crate::entity::bot_group::BotGroup;
 use crate::entity::bot_group_member::BotGroupMember;
+use crate::entity::bot_user_info::{BotUserInfo, UserKey};
+use crate::entity::sys_face::{SysFaceEntry, SysFacePackEntry};
 use crate::{ManiaError, ManiaResult, dda};
 use dashmap::DashMap;
 use std::borrow::Cow;
@@ -208,6 +214,11 @@ impl BusinessHandle {
             Ok(None::<()>)
         })
         .await?;
+        let cached_friend_categories = self.cache.cached_friend_categories.as_ref().unwrap();
+        cached_friend_categories.clear();
+        for (id, name) in friend_groups {
+            cached_friend_categories.insert(id, name);
+        }
         let cached_friends = self.cache.cached_friends.as_ref().unwrap();
         cached_friends.clear();
         for (uin, friend) in friends.iter() {
@@ -400,4 +411,150 @@ impl BusinessHandle {
             Ok(process_fn(&friends))
         }
     }
+
+    async fn fetch_groups(self: &Arc<Self>) -> ManiaResult<Vec<BotGroup>> {
+        let mut event = FetchGroupsEvent::default();
+        let mut result = self.send_event(&mut event).await?;
+        let event: &mut FetchGroupsEvent = downcast_mut_major_event(&mut result)
+            .ok_or_else(|| ManiaError::GenericError("Downcast error".into()))?;
+        Ok(std::mem::take(&mut event.groups))
+    }
+
+    pub(crate) async fn refresh_groups_cache(self: &Arc<Self>) -> ManiaResult<()> {
+        if self.cache.cache_mode == CacheMode::None {
+            tracing::warn!("Cache mode is None, no need to refresh groups cache");
+            return Ok(());
+        }
+        let groups = self.fetch_groups().await?;
+        let cached_groups = self.cache.cached_groups.as_ref().unwrap();
+        let remarks: HashMap<u32, String> = cached_groups
+            .iter()
+            .filter_map(|entry| Some((*entry.key(), entry.value().remark.clone()?)))
+            .collect();
+        cached_groups.clear();
+        for mut group in groups {
+            group.remark = remarks.get(&group.group_uin).cloned();
+            cached_groups.insert(group.group_uin, group);
+        }
+        Ok(())
+    }
+
+    // TODO: Optimize performance in no-cache mode
+    pub async fn fetch_maybe_cached_groups<F>(
+        self: &Arc<Self>,
+        maybe_group_uin: Option<u32>,
+        process_fn: F,
+        refresh_cache: bool,
+    ) -> ManiaResult<Vec<BotGroup>>
+    where
+        F: Fn(&DashMap<u32, BotGroup>) -> Vec<BotGroup>,
+    {
+        if self.cache.cache_mode != CacheMode::None {
+            let cached_groups = self.cache.cached_groups.as_ref().unwrap();
+            if refresh_cache
+                || cached_groups.is_empty()
+                || maybe_group_uin.is_some_and(|uin| !cached_groups.contains_key(&uin))
+            {
+                self.refresh_groups_cache().await?;
+            }
+            Ok(process_fn(cached_groups))
+        } else {
+            let groups: DashMap<u32, BotGroup> = self
+                .fetch_groups()
+                .await?
+                .into_iter()
+                .map(|group| (group.group_uin, group))
+                .collect();
+            Ok(process_fn(&groups))
+        }
+    }
+
+    pub async fn fetch_sys_faces(self: &Arc<Self>) -> ManiaResult<Vec<SysFacePackEntry>> {
+        let mut event = FetchSysFacesEvent::default();
+        let mut result = self.send_event(&mut event).await?;
+        let event: &mut FetchSysFacesEvent = downcast_mut_major_event(&mut result)
+            .ok_or_else(|| ManiaError::GenericError("Downcast error".into()))?;
+        Ok(std::mem::take(&mut event.face_packs))
+    }
+
+    pub(crate) async fn refresh_sys_faces_cache(self: &Arc<Self>) -> ManiaResult<()> {
+        if self.cache.cache_mode == CacheMode::None {
+            tracing::warn!("Cache mode is None, no need to refresh sys faces cache");
+            return Ok(());
+        }
+        let face_packs = self.fetch_sys_faces().await?;
+        let cached_sys_faces = self.cache.cached_sys_faces.as_ref().unwrap();
+        cached_sys_faces.clear();
+        for face in face_packs.into_iter().flat_map(|pack| pack.emojis) {
+            if let Ok(face_id) = face.q_sid.parse::<u32>() {
+                cached_sys_faces.insert(face_id, face);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn fetch_maybe_cached_sys_face(
+        self: &Arc<Self>,
+        face_id: u32,
+        refresh_cache: bool,
+    ) -> ManiaResult<Option<SysFaceEntry>> {
+        if self.cache.cache_mode != CacheMode::None {
+            let cached_sys_faces = self.cache.cached_sys_faces.as_ref().unwrap();
+            if refresh_cache || cached_sys_faces.is_empty() {
+                self.refresh_sys_faces_cache().await?;
+            }
+            Ok(cached_sys_faces
+                .get(&face_id)
+                .map(|entry| entry.value().clone()))
+        } else {
+            Ok(self
+                .fetch_sys_faces()
+                .await?
+                .into_iter()
+                .flat_map(|pack| pack.emojis)
+                .find(|face| face.q_sid.parse::<u32>().is_ok_and(|id| id == face_id)))
+        }
+    }
+
+    /// Fetch the profile of any user by uin or uid, cached by uin unless the cache mode is `None`
+    pub async fn fetch_user_info(
+        self: &Arc<Self>,
+        user: impl Into<UserKey>,
+        refresh_cache: bool,
+    ) -> ManiaResult<BotUserInfo> {
+        let user = user.into();
+        let cached_user_info = self.cache.cached_user_info.as_ref();
+        if let Some(cached_user_info) = cached_user_info
+            && !refresh_cache
+        {
+            let uin = match &user {
+                UserKey::Uin(uin) => Some(*uin),
+                UserKey::Uid(uid) => self
+                    .cache
+                    .uid2uin
+                    .as_ref()
+                    .and_then(|uid2uin| uid2uin.get(uid).map(|uin| *uin)),
+            };
+            if let Some(info) = uin.and_then(|uin| cached_user_info.get(&uin)) {
+                return Ok(info.value().clone());
+            }
+        }
+        let mut event = match &user {
+            UserKey::Uin(uin) => dda!(FetchUserInfoEvent { uin: *uin }),
+            UserKey::Uid(uid) => dda!(FetchUserInfoEvent {
+                uid: Some(uid.clone()),
+            }),
+        };
+        let mut result = self.send_event(&mut event).await?;
+        let event: &mut FetchUserInfoEvent = downcast_mut_major_event(&mut result)
+            .ok_or_else(|| ManiaError::GenericError("Downcast error".into()))?;
+        let info = std::mem::take(&mut event.user_info);
+        if let Some(cached_user_info) = cached_user_info {
+            if let UserKey::Uid(uid) = user {
+                self.cache.insert_uin_uid(info.uin, uid);
+            }
+            cached_user_info.insert(info.uin, info.clone());
+        }
+        Ok(info)
+    }
 }
@@ -1,26 +1,40 @@
 use crate::core::business::BusinessHandle;
+use crate::core::event::action::set_status::SetStatusEvent;
 use crate::core::event::message::file_c2c_download::FileC2CDownloadEvent;
 use crate::core::event::message::file_group_download::FileGroupDownloadEvent;
 use crate::core::event::message::image_c2c_download::ImageC2CDownloadEvent;
 use crate::core::event::message::image_group_download::ImageGroupDownloadEvent;
+use crate::core::event::message::mark_read::MarkReadEvent;
 use crate::core::event::message::multi_msg_download::MultiMsgDownloadEvent;
+use crate::core::event::message::multi_msg_upload::MultiMsgUploadEvent;
 use crate::core::event::message::record_c2c_download::RecordC2CDownloadEvent;
 use crate::core::event::message::record_group_download::RecordGroupDownloadEvent;
 use crate::core::event::message::send_message::SendMessageEvent;
 use crate::core::event::message::video_c2c_download::VideoC2CDownloadEvent;
 use crate::core::event::message::video_group_download::VideoGroupDownloadEvent;
+use crate::core::event::system::face_roam::FaceRoamEvent;
 use crate::core::event::system::fetch_filtered_group_request::FetchFilteredGroupRequestsEvent;
 use crate::core::event::system::fetch_group_requests::FetchGroupRequestsEvent;
 use crate::core::event::system::fetch_rkey::FetchRKeyEvent;
-use crate::core::event::system::fetch_user_info::FetchUserInfoEvent;
 use crate::core::event::{downcast_major_event, downcast_mut_major_event};
+use crate::core::highway::AsyncPureStream;
 use crate::core::protos::service::oidb::IndexNode;
 use crate::entity::bot_group_request::BotGroupRequest;
-use crate::message::chain::MessageChain;
+use crate::entity::online_status::{CustomStatus, OnlineStatus};
+use crate::message::chain::{
+    FriendMessageUniqueElem, GroupMessageUniqueElem, MessageChain, MessageType,
+    TempMessageUniqueElem,
+};
+use crate::message::entity::Entity;
+use crate::message::entity::image::ImageEntity;
+use crate::message::entity::long_msg::LongMsgEntity;
 use crate::{ManiaError, ManiaResult, dda};
 use futures::future::join_all;
+use std::borrow::Cow;
+use std::io::Cursor;
 use std::sync::Arc;
 use tokio::join;
+use tokio::sync::Mutex;
 
 impl BusinessHandle {
     pub async fn fetch_rkey(self: &Arc<Self>) -> ManiaResult<()> {
@@ -82,6 +96,21 @@ impl BusinessHandle {
         Ok(event.chains.take())
     }
 
+    pub async fn multi_msg_upload(
+        self: &Arc<Self>,
+        group_uin: Option<u32>,
+        chains: Vec<MessageChain>,
+    ) -> ManiaResult<(String, Vec<MessageChain>)> {
+        let mut upload_event = dda!(MultiMsgUploadEvent { group_uin, chains });
+        let mut res = self.send_event(&mut upload_event).await?;
+        let event: &mut MultiMsgUploadEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        let res_id = event.res_id.take().ok_or_else(|| {
+            ManiaError::GenericError(Cow::from("Missing res_id in MultiMsgUploadEvent"))
+        })?;
+        Ok((res_id, upload_event.chains))
+    }
+
     pub(crate) async fn download_group_file(
         self: &Arc<Self>,
         group_uin: u32,
@@ -250,13 +279,7 @@ impl BusinessHandle {
         self: &Arc<Self>,
         stranger_uid: &str,
     ) -> ManiaResult<u32> {
-        let mut fetch_event = dda!(FetchUserInfoEvent {
-            uid: Some(stranger_uid.to_string()),
-        });
-        let res = self.send_event(&mut fetch_event).await?;
-        let event: &FetchUserInfoEvent =
-            downcast_major_event(&res).ok_or(ManiaError::InternalEventDowncastError)?;
-        Ok(event.uin)
+        Ok(self.fetch_user_info(stranger_uid, false).await?.uin)
     }
 
     pub(crate) async fn resolve_stranger_uid2uin_fast(self: &Arc<Self>, stranger_uid: &str) -> u32 {
@@ -323,9 +346,223 @@ impl BusinessHandle {
     }
 
     pub async fn send_message(self: &Arc<Self>, message: MessageChain) -> ManiaResult<()> {
+        if let MessageType::Temp(temp) = &message.typ
+            && temp.group_uin == 0
+        {
+            return Err(ManiaError::GenericError(Cow::from(
+                "temp message has no group, set the group the session was started from",
+            )));
+        }
         let mut send_event = dda!(SendMessageEvent { chain: message });
         let res = self.send_event(&mut send_event).await?;
         tracing::info!("Send message: {:?}", res);
         Ok(())
     }
+
+    /// Set the status shown to friends, `ext_status` is usually 0
+    pub async fn set_online_status(
+        self: &Arc<Self>,
+        status: OnlineStatus,
+        ext_status: u32,
+    ) -> ManiaResult<()> {
+        self.set_status(dda!(SetStatusEvent {
+            status: status as u32,
+            ext_status,
+            battery_status: 100,
+        }))
+        .await
+    }
+
+    /// Set a custom status with a face from the system faces and a wording
+    pub async fn set_custom_status(
+        self: &Arc<Self>,
+        face_id: u32,
+        wording: &str,
+    ) -> ManiaResult<()> {
+        self.set_status(dda!(SetStatusEvent {
+            status: OnlineStatus::Online as u32,
+            ext_status: CustomStatus::EXT_STATUS,
+            battery_status: 100,
+            custom: Some(CustomStatus::new(face_id, wording)),
+        }))
+        .await
+    }
+
+    async fn set_status(self: &Arc<Self>, mut event: SetStatusEvent) -> ManiaResult<()> {
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut SetStatusEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        match event.message.is_empty() {
+            true => Ok(()),
+            false => Err(ManiaError::GenericError(Cow::from(format!(
+                "Set status failed: {}",
+                event.message
+            )))),
+        }
+    }
+
+    /// Mark the messages of a group up to `sequence` as read
+    pub async fn mark_group_read(
+        self: &Arc<Self>,
+        group_uin: u32,
+        sequence: u32,
+    ) -> ManiaResult<()> {
+        let mut event = dda!(MarkReadEvent {
+            group_uin: Some(group_uin),
+            start_sequence: sequence,
+        });
+        self.send_event(&mut event).await?;
+        Ok(())
+    }
+
+    /// Mark the messages of a friend up to `time` (unix timestamp in seconds) as read
+    pub async fn mark_friend_read(
+        self: &Arc<Self>,
+        friend_uid: &str,
+        time: u32,
+    ) -> ManiaResult<()> {
+        let mut event = dda!(MarkReadEvent {
+            target_uid: Some(friend_uid.to_string()),
+            time,
+        });
+        self.send_event(&mut event).await?;
+        Ok(())
+    }
+
+    /// Send `chain` to `member_uin` through the temporary session of `group_uin`, no
+    /// matter which chat the chain was built for
+    pub async fn send_temp_message(
+        self: &Arc<Self>,
+        group_uin: u32,
+        member_uin: u32,
+        mut chain: MessageChain,
+    ) -> ManiaResult<()> {
+        chain.typ = MessageType::Temp(TempMessageUniqueElem { group_uin });
+        if chain.friend_uin != member_uin {
+            chain.friend_uin = member_uin;
+            chain.uid.clear();
+        }
+        self.send_message(chain).await
+    }
+
+    /// Upload the entities of the chain as a long message, and replace them with its reference
+    pub(crate) async fn pack_long_message(
+        self: &Arc<Self>,
+        mut chain: MessageChain,
+    ) -> ManiaResult<MessageChain> {
+        let group_uin = match &chain.typ {
+            MessageType::Group(group) => Some(group.group_uin),
+            _ => None,
+        };
+        let inner = dda!(MessageChain {
+            typ: match group_uin {
+                Some(group_uin) => MessageType::Group(dda!(GroupMessageUniqueElem { group_uin })),
+                None => MessageType::Friend(FriendMessageUniqueElem::default()),
+            },
+            friend_uin: **self.context.key_store.uin.load(),
+            time: chain.time,
+            entities: std::mem::take(&mut chain.entities),
+        });
+        let (res_id, mut chains) = self.multi_msg_upload(group_uin, vec![inner]).await?;
+        chain.entities.push(Entity::LongMsg(LongMsgEntity {
+            res_id,
+            chain: chains.pop().unwrap_or_default(),
+        }));
+        Ok(chain)
+    }
+
+    /// Fetch the favorite stickers of the bot, only `url` of the returned images is set, use
+    /// `download_favorite_sticker` to get a sendable one
+    pub async fn fetch_favorite_stickers(self: &Arc<Self>) -> ManiaResult<Vec<ImageEntity>> {
+        let mut event = FaceRoamEvent::default();
+        let res = self.send_event(&mut event).await?;
+        let event: &FaceRoamEvent =
+            downcast_major_event(&res).ok_or(ManiaError::InternalEventDowncastError)?;
+        favorite_stickers(event, **self.context.key_store.uin.load())
+    }
+
+    /// Download a favorite sticker into an image that can be sent with
+    /// `MessageChainBuilder::sticker`
+    pub async fn download_favorite_sticker(
+        self: &Arc<Self>,
+        sticker: &ImageEntity,
+    ) -> ManiaResult<ImageEntity> {
+        let data = self
+            .web_client()
+            .get_binary_async(&sticker.url, None)
+            .await
+            .map_err(|e| {
+                ManiaError::GenericError(Cow::from(format!("Download sticker failed: {:?}", e)))
+            })?;
+        Ok(dda!(ImageEntity {
+            size: data.len() as u32,
+            url: sticker.url.clone(),
+            image_stream: Some(Arc::new(Mutex::new(
+                Box::new(Cursor::new(data)) as AsyncPureStream
+            ))),
+            summary: Some("[动画表情]".to_string()),
+            sub_type: 1,
+        }))
+    }
+}
+
+/// The stickers listed in the face roam response of `uin`
+fn favorite_stickers(event: &FaceRoamEvent, uin: u32) -> ManiaResult<Vec<ImageEntity>> {
+    if event.ret_code != 0 {
+        return Err(ManiaError::GenericError(Cow::from(format!(
+            "Face roam failed: {} {}",
+            event.ret_code, event.err_msg
+        ))));
+    }
+    Ok(event
+        .file_names
+        .iter()
+        .map(|file_name| {
+            dda!(ImageEntity {
+                url: format!("https://p.qpic.cn/{}/{}/{}/0", event.bid, uin, file_name),
+                summary: Some("[动画表情]".to_string()),
+                sub_type: 1,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ClientConfig;
+    use crate::core::context::Context;
+    use crate::core::event::ClientEvent;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_favorite_stickers_decoded() {
+        const FILE_NAMES: [&str; 2] = [
+            "10000_0_0_0_0123456789ABCDEF0123456789ABCDEF_0_0",
+            "10000_0_0_0_FEDCBA9876543210FEDCBA9876543210_0_0",
+        ];
+        // assembled by hand after the FaceRoamResponse layout, there is no capture at hand:
+        // RetCode 0, SubCmd 1 and a UserInfo listing two file names, Bid "qq_expression",
+        // MaxRoamSize 300 and EmojiType [1, 1]
+        let mut user_info = Vec::new();
+        for file_name in FILE_NAMES {
+            user_info.extend([0x0a, file_name.len() as u8]);
+            user_info.extend(file_name.as_bytes());
+        }
+        user_info.extend(b"\x1a\x0dqq_expression\x20\xac\x02\x2a\x02\x01\x01");
+        let mut packet = vec![0x08, 0x00, 0x18, 0x01, 0x32, user_info.len() as u8];
+        packet.extend(user_info);
+
+        let ctx = Context::for_test(ClientConfig::default());
+        let res = FaceRoamEvent::parse(Bytes::from(packet), &ctx).unwrap();
+        let event: &FaceRoamEvent = downcast_major_event(&res).unwrap();
+        assert_eq!(event.bid, "qq_expression");
+        let stickers = favorite_stickers(event, 10000).unwrap();
+        assert_eq!(
+            stickers.iter().map(|s| s.url.as_str()).collect::<Vec<_>>(),
+            FILE_NAMES
+                .map(|file_name| format!("https://p.qpic.cn/qq_expression/10000/{file_name}/0"))
+        );
+        assert!(stickers.iter().all(|s| s.sub_type == 1));
+    }
 }
@@ -0,0 +1,193 @@
+use crate::core::business::BusinessHandle;
+use crate::core::event::action::friend_category::{
+    CreateFriendCategoryEvent, DeleteFriendCategoryEvent, MoveFriendCategoryEvent,
+    RenameFriendCategoryEvent,
+};
+use crate::core::event::action::request_friend::RequestFriendEvent;
+use crate::core::event::action::set_friend_remark::SetFriendRemarkEvent;
+use crate::core::event::downcast_mut_major_event;
+use crate::core::event::system::fetch_friend_verification::FetchFriendVerificationEvent;
+use crate::entity::bot_friend::{BotFriend, BotFriendGroup};
+use crate::entity::join_verification::JoinVerification;
+use crate::{ManiaError, ManiaResult, dda};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+impl BusinessHandle {
+    /// Fetch how friend requests to `uin` are verified
+    pub async fn fetch_friend_verification(
+        self: &Arc<Self>,
+        uin: u32,
+    ) -> ManiaResult<JoinVerification> {
+        let mut event = FetchFriendVerificationEvent {
+            target_uin: uin,
+            verification: JoinVerification::NeedVerify,
+        };
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut FetchFriendVerificationEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        Ok(event.verification.clone())
+    }
+
+    /// Send a friend request to `uin`, `answer` is required if the target asks a question.
+    /// Returns the verification of the target, so callers know whether a review follows
+    pub async fn send_friend_request(
+        self: &Arc<Self>,
+        uin: u32,
+        message: &str,
+        answer: Option<&str>,
+    ) -> ManiaResult<JoinVerification> {
+        let verification = self.fetch_friend_verification(uin).await?;
+        check_verification(&verification, answer)?;
+        let mut event = RequestFriendEvent {
+            target_uin: uin,
+            message: message.to_string(),
+            answer: answer.map(str::to_string),
+        };
+        self.send_event(&mut event).await?;
+        Ok(verification)
+    }
+
+    /// Create a friend category, returns the id of the new category
+    pub async fn create_friend_category(self: &Arc<Self>, name: &str) -> ManiaResult<u32> {
+        let mut event = dda!(CreateFriendCategoryEvent {
+            name: name.to_string(),
+        });
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut CreateFriendCategoryEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        let category_id = event.category_id;
+        if let Some(categories) = self.cache.cached_friend_categories.as_ref() {
+            categories.insert(category_id, name.to_string());
+        }
+        Ok(category_id)
+    }
+
+    pub async fn rename_friend_category(
+        self: &Arc<Self>,
+        category_id: u32,
+        name: &str,
+    ) -> ManiaResult<()> {
+        let mut event = RenameFriendCategoryEvent {
+            category_id,
+            name: name.to_string(),
+        };
+        self.send_event(&mut event).await?;
+        if let Some(categories) = self.cache.cached_friend_categories.as_ref() {
+            categories.insert(category_id, name.to_string());
+        }
+        self.patch_cached_friends(
+            |friend| {
+                friend
+                    .group
+                    .as_ref()
+                    .is_some_and(|g| g.group_id == category_id)
+            },
+            category_id,
+        );
+        Ok(())
+    }
+
+    /// Delete a friend category, the friends in it are moved to the default category
+    pub async fn delete_friend_category(self: &Arc<Self>, category_id: u32) -> ManiaResult<()> {
+        let mut event = DeleteFriendCategoryEvent { category_id };
+        self.send_event(&mut event).await?;
+        if let Some(categories) = self.cache.cached_friend_categories.as_ref() {
+            categories.remove(&category_id);
+        }
+        self.patch_cached_friends(
+            |friend| {
+                friend
+                    .group
+                    .as_ref()
+                    .is_some_and(|g| g.group_id == category_id)
+            },
+            0,
+        );
+        Ok(())
+    }
+
+    pub async fn move_friend_category(
+        self: &Arc<Self>,
+        friend_uin: u32,
+        category_id: u32,
+    ) -> ManiaResult<()> {
+        let mut event = MoveFriendCategoryEvent {
+            friend_uid: self.uin2uid(friend_uin, None).await?,
+            category_id,
+        };
+        self.send_event(&mut event).await?;
+        self.patch_cached_friends(|friend| friend.uin == friend_uin, category_id);
+        Ok(())
+    }
+
+    pub async fn set_friend_remark(
+        self: &Arc<Self>,
+        friend_uin: u32,
+        remark: &str,
+    ) -> ManiaResult<()> {
+        let mut event = SetFriendRemarkEvent {
+            friend_uid: self.uin2uid(friend_uin, None).await?,
+            remark: remark.to_string(),
+        };
+        self.send_event(&mut event).await?;
+        if let Some(mut friend) = self
+            .cache
+            .cached_friends
+            .as_ref()
+            .and_then(|friends| friends.get_mut(&friend_uin))
+        {
+            friend.remarks = remark.to_string();
+        }
+        Ok(())
+    }
+
+    /// Point the category of the cached friends matching `filter` to `category_id`
+    fn patch_cached_friends(
+        self: &Arc<Self>,
+        filter: impl Fn(&BotFriend) -> bool,
+        category_id: u32,
+    ) {
+        let (Some(friends), Some(categories)) = (
+            self.cache.cached_friends.as_ref(),
+            self.cache.cached_friend_categories.as_ref(),
+        ) else {
+            return;
+        };
+        let group_name = categories
+            .get(&category_id)
+            .map(|name| name.value().clone())
+            .unwrap_or_default();
+        friends
+            .iter_mut()
+            .filter(|friend| filter(friend.value()))
+            .for_each(|mut friend| {
+                friend.group = Some(BotFriendGroup {
+                    group_id: category_id,
+                    group_name: group_name.clone(),
+                });
+            });
+    }
+}
+
+/// Reject requests that can not succeed before sending them
+pub(super) fn check_verification(
+    verification: &JoinVerification,
+    answer: Option<&str>,
+) -> ManiaResult<()> {
+    match verification {
+        JoinVerification::RejectAll => Err(ManiaError::GenericError(Cow::from(
+            "The target rejects all requests",
+        ))),
+        JoinVerification::AnswerQuestion(question)
+        | JoinVerification::QuestionAndVerify(question)
+            if answer.is_none_or(str::is_empty) =>
+        {
+            Err(ManiaError::GenericError(Cow::from(format!(
+                "An answer is required for question: {}",
+                question
+            ))))
+        }
+        _ => Ok(()),
+    }
+}
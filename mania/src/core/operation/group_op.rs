@@ -0,0 +1,299 @@
+use crate::core::business::BusinessHandle;
+use crate::core::cache::CacheMode;
+use crate::core::event::action::create_group::CreateGroupEvent;
+use crate::core::event::action::group_todo::{
+    CompleteGroupTodoEvent, RemoveGroupTodoEvent, SetGroupTodoEvent,
+};
+use crate::core::event::action::rename_group::RenameGroupEvent;
+use crate::core::event::action::request_join_group::RequestJoinGroupEvent;
+use crate::core::event::action::set_group_remark::SetGroupRemarkEvent;
+use crate::core::event::action::set_pinned::SetPinnedEvent;
+use crate::core::event::downcast_mut_major_event;
+use crate::core::event::system::fetch_group_detail::FetchGroupDetailEvent;
+use crate::core::operation::friend_op::check_verification;
+use crate::core::protos::service::highway::{GroupAvatarExtra, GroupAvatarExtraField3};
+use crate::entity::bot_group_member::MutedGroupMember;
+use crate::entity::join_verification::JoinVerification;
+use crate::event::group::GroupEvent;
+use crate::event::group::group_name_change::GroupNameChangeEvent;
+use crate::event::group::group_pin_changed::{ChatType, PinChangedEvent};
+use crate::event::group::group_todo::{GroupTodoAction, GroupTodoEvent};
+use crate::{ManiaError, ManiaResult, dda};
+use bytes::Bytes;
+use chrono::Utc;
+use prost::Message;
+use std::borrow::Cow;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncSeek};
+
+impl BusinessHandle {
+    /// Create a group with the friends in `initial_members`, returns the uin of the new group
+    pub async fn create_group(
+        self: &Arc<Self>,
+        name: &str,
+        initial_members: &[u32],
+    ) -> ManiaResult<u32> {
+        let member_uids = self.resolve_friend_uids(initial_members).await?;
+        let mut event = dda!(CreateGroupEvent {
+            name: name.to_string(),
+            member_uids,
+        });
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut CreateGroupEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        if event.group_uin == 0 {
+            return Err(ManiaError::GenericError(Cow::from(
+                "Create group failed: no group uin in response",
+            )));
+        }
+        let group_uin = event.group_uin;
+        self.populate_group_cache(group_uin).await;
+        Ok(group_uin)
+    }
+
+    /// Invite friends in `friend_uins` into the group
+    pub async fn invite_to_group(
+        self: &Arc<Self>,
+        group_uin: u32,
+        friend_uins: &[u32],
+    ) -> ManiaResult<()> {
+        let member_uids = self.resolve_friend_uids(friend_uins).await?;
+        let mut event = dda!(CreateGroupEvent {
+            group_uin,
+            member_uids,
+        });
+        self.send_event(&mut event).await?;
+        self.populate_group_cache(group_uin).await;
+        Ok(())
+    }
+
+    /// Fetch how join requests to the group are verified
+    pub async fn fetch_group_verification(
+        self: &Arc<Self>,
+        group_uin: u32,
+    ) -> ManiaResult<JoinVerification> {
+        let mut event = FetchGroupDetailEvent {
+            group_uin,
+            group_name: String::new(),
+            member_count: 0,
+            max_member: 0,
+            verification: JoinVerification::NeedVerify,
+        };
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut FetchGroupDetailEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        Ok(event.verification.clone())
+    }
+
+    /// Request to join the group, `message` is taken as the answer if the group asks a
+    /// question. Returns the verification of the group, so callers know whether a review
+    /// follows
+    pub async fn request_join_group(
+        self: &Arc<Self>,
+        group_uin: u32,
+        message: &str,
+    ) -> ManiaResult<JoinVerification> {
+        let verification = self.fetch_group_verification(group_uin).await?;
+        check_verification(&verification, Some(message))?;
+        let mut event = RequestJoinGroupEvent {
+            group_uin,
+            message: message.to_string(),
+        };
+        self.send_event(&mut event).await?;
+        Ok(verification)
+    }
+
+    async fn resolve_friend_uids(self: &Arc<Self>, uins: &[u32]) -> ManiaResult<Vec<String>> {
+        let mut uids = Vec::with_capacity(uins.len());
+        for &uin in uins {
+            uids.push(self.uin2uid(uin, None).await?);
+        }
+        Ok(uids)
+    }
+
+    /// Refresh the cached group and its members after membership changes, failures are only
+    /// logged as the operation itself has succeeded
+    async fn populate_group_cache(self: &Arc<Self>, group_uin: u32) {
+        if self.cache.cache_mode == CacheMode::None {
+            return;
+        }
+        if let Err(e) = self.refresh_groups_cache().await {
+            tracing::warn!("Failed to refresh groups cache: {:?}", e);
+        }
+        if let Err(e) = self.refresh_group_members_cache(group_uin).await {
+            tracing::warn!(
+                "Failed to refresh group {} members cache: {:?}",
+                group_uin,
+                e
+            );
+        }
+    }
+
+    /// Members currently muted in the group, the member list is always refetched as mutes
+    /// expire on their own
+    pub async fn fetch_muted_members(
+        self: &Arc<Self>,
+        group_uin: u32,
+    ) -> ManiaResult<Vec<MutedGroupMember>> {
+        let members = self
+            .fetch_maybe_cached_group_members(
+                group_uin,
+                |map| map.get(&group_uin).map(|m| m.clone()).unwrap_or_default(),
+                true,
+            )
+            .await?;
+        let now = Utc::now();
+        Ok(members
+            .into_iter()
+            .filter_map(|member| {
+                let mute_until = member.shut_up_timestamp.filter(|ts| *ts > now)?;
+                Some(MutedGroupMember {
+                    uin: member.uin,
+                    uid: member.uid,
+                    member_card: member.member_card,
+                    member_name: member.member_name,
+                    mute_until,
+                })
+            })
+            .collect())
+    }
+
+    pub async fn rename_group(self: &Arc<Self>, group_uin: u32, name: &str) -> ManiaResult<()> {
+        let mut event = RenameGroupEvent {
+            group_uin,
+            name: name.to_string(),
+        };
+        self.send_event(&mut event).await?;
+        self.cache
+            .patch_group(group_uin, |group| group.group_name = name.to_string());
+        if let Err(e) = self
+            .event_dispatcher
+            .group
+            .send(Some(GroupEvent::GroupNameChange(GroupNameChangeEvent {
+                group_uin,
+                name: name.to_string(),
+            })))
+        {
+            tracing::error!("Failed to send group name change event: {:?}", e);
+        }
+        Ok(())
+    }
+
+    /// Upload `avatar` (an image file content) as the avatar of the group
+    pub async fn set_group_avatar<S>(self: &Arc<Self>, group_uin: u32, avatar: S) -> ManiaResult<()>
+    where
+        S: AsyncRead + AsyncSeek + Send + Sync + Unpin + 'static,
+    {
+        let extra = GroupAvatarExtra {
+            r#type: 101,
+            group_uin,
+            field3: Some(GroupAvatarExtraField3 { field1: 1 }),
+            field5: 3,
+            field6: 1,
+        };
+        self.upload_src_stream(3000, Box::new(avatar), Bytes::from(extra.encode_to_vec()))
+            .await?;
+        tracing::debug!("Successfully uploaded group avatar!");
+        Ok(())
+    }
+
+    /// Set the remark of the group, which is only visible to the bot
+    pub async fn set_group_remark(
+        self: &Arc<Self>,
+        group_uin: u32,
+        remark: &str,
+    ) -> ManiaResult<()> {
+        let mut event = SetGroupRemarkEvent {
+            group_uin,
+            remark: remark.to_string(),
+        };
+        self.send_event(&mut event).await?;
+        self.cache
+            .patch_group(group_uin, |group| group.remark = Some(remark.to_string()));
+        Ok(())
+    }
+
+    /// Set the message at `sequence` as the todo of the group
+    pub async fn set_group_todo(
+        self: &Arc<Self>,
+        group_uin: u32,
+        sequence: u32,
+    ) -> ManiaResult<()> {
+        let mut event = SetGroupTodoEvent {
+            group_uin,
+            sequence,
+        };
+        self.send_event(&mut event).await?;
+        self.dispatch_local_todo(group_uin, GroupTodoAction::Set);
+        Ok(())
+    }
+
+    /// Mark the todo of the group as finished
+    pub async fn complete_group_todo(self: &Arc<Self>, group_uin: u32) -> ManiaResult<()> {
+        let mut event = CompleteGroupTodoEvent { group_uin };
+        self.send_event(&mut event).await?;
+        self.dispatch_local_todo(group_uin, GroupTodoAction::Complete);
+        Ok(())
+    }
+
+    /// Remove the todo of the group without finishing it
+    pub async fn remove_group_todo(self: &Arc<Self>, group_uin: u32) -> ManiaResult<()> {
+        let mut event = RemoveGroupTodoEvent { group_uin };
+        self.send_event(&mut event).await?;
+        self.dispatch_local_todo(group_uin, GroupTodoAction::Remove);
+        Ok(())
+    }
+
+    fn dispatch_local_todo(self: &Arc<Self>, group_uin: u32, action: GroupTodoAction) {
+        if let Err(e) =
+            self.event_dispatcher
+                .group
+                .send(Some(GroupEvent::GroupTodo(GroupTodoEvent {
+                    group_uin,
+                    operator_uin: **self.context.key_store.uin.load(),
+                    action,
+                })))
+        {
+            tracing::error!("Failed to send group todo event: {:?}", e);
+        }
+    }
+
+    /// Pin or unpin the chat of a friend or a group, `uin` is the friend uin or the group uin.
+    /// The resulting [`PinChangedEvent`] goes to the group event channel for friend chats too,
+    /// like the pin changes pushed by the server, `chat_type` tells them apart
+    pub async fn set_chat_pinned(
+        self: &Arc<Self>,
+        chat_type: ChatType,
+        uin: u32,
+        pinned: bool,
+    ) -> ManiaResult<()> {
+        let mut event = match chat_type {
+            ChatType::Friend => dda!(SetPinnedEvent {
+                friend_uid: Some(self.uin2uid(uin, None).await?),
+                is_pin: pinned,
+            }),
+            ChatType::Group => dda!(SetPinnedEvent {
+                group_uin: Some(uin),
+                is_pin: pinned,
+            }),
+            ChatType::Service => {
+                return Err(ManiaError::GenericError(Cow::from(
+                    "Pinning service chats is not supported",
+                )));
+            }
+        };
+        self.send_event(&mut event).await?;
+        if let Err(e) = self
+            .event_dispatcher
+            .group
+            .send(Some(GroupEvent::GroupPinChanged(PinChangedEvent {
+                chat_type,
+                uin,
+                is_pin: pinned,
+            })))
+        {
+            tracing::error!("Failed to send group pin change event: {:?}", e);
+        }
+        Ok(())
+    }
+}
@@ -69,6 +69,28 @@ impl BusinessHandle {
         Ok(())
     }
 
+    /// Upload the whole `stream` to highway with `cmd`, for uploads without a rich media request
+    pub(super) async fn upload_src_stream(
+        self: &Arc<Self>,
+        cmd: u32,
+        mut stream: AsyncPureStream,
+        ext_info: Bytes,
+    ) -> ManiaResult<()> {
+        self.prepare_highway().await?;
+        let mut md5_hasher = Md5::new();
+        let mut size = 0;
+        stream_pipeline(&mut stream, |chunk| {
+            md5_hasher.update(chunk);
+            size += chunk.len() as u32;
+        })
+        .await?;
+        stream.rewind().await?;
+        let md5 = Bytes::from(md5_hasher.finalize().to_vec());
+        let client = self.highway.client.load();
+        client.upload(cmd, &mut stream, size, md5, ext_info).await?;
+        Ok(())
+    }
+
     async fn resolve_image(
         self: &Arc<Self>,
         stream_ctx: AsyncStream,
@@ -1,5 +1,9 @@
 mod cache_op;
 mod common_op;
+mod friend_op;
+mod group_op;
 mod highway_op;
+mod profile_op;
+mod web_op;
 mod wt_op;
 // TODO: add prelude
@@ -0,0 +1,62 @@
+use crate::core::business::BusinessHandle;
+use crate::core::event::system::set_profile::SetProfileEvent;
+use crate::entity::bot_user_info::GenderInfo;
+use crate::{ManiaResult, dda};
+use bytes::Bytes;
+use chrono::NaiveDate;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncSeek};
+
+impl BusinessHandle {
+    pub async fn set_nickname(self: &Arc<Self>, nickname: &str) -> ManiaResult<()> {
+        self.set_profile(dda!(SetProfileEvent {
+            nickname: Some(nickname.to_string()),
+        }))
+        .await?;
+        self.context
+            .key_store
+            .info
+            .load()
+            .name
+            .store(Arc::new(nickname.to_string()));
+        Ok(())
+    }
+
+    pub async fn set_personal_signature(self: &Arc<Self>, signature: &str) -> ManiaResult<()> {
+        self.set_profile(dda!(SetProfileEvent {
+            signature: Some(signature.to_string()),
+        }))
+        .await
+    }
+
+    pub async fn set_gender(self: &Arc<Self>, gender: GenderInfo) -> ManiaResult<()> {
+        self.set_profile(dda!(SetProfileEvent {
+            gender: Some(gender),
+        }))
+        .await
+    }
+
+    pub async fn set_birthday(self: &Arc<Self>, birthday: NaiveDate) -> ManiaResult<()> {
+        self.set_profile(dda!(SetProfileEvent {
+            birthday: Some(birthday),
+        }))
+        .await
+    }
+
+    async fn set_profile(self: &Arc<Self>, mut event: SetProfileEvent) -> ManiaResult<()> {
+        event.uin = **self.context.key_store.uin.load();
+        self.send_event(&mut event).await?;
+        Ok(())
+    }
+
+    /// Upload `avatar` (an image file content) as the avatar of the bot
+    pub async fn set_avatar<S>(self: &Arc<Self>, avatar: S) -> ManiaResult<()>
+    where
+        S: AsyncRead + AsyncSeek + Send + Sync + Unpin + 'static,
+    {
+        self.upload_src_stream(90, Box::new(avatar), Bytes::new())
+            .await?;
+        tracing::debug!("Successfully uploaded avatar!");
+        Ok(())
+    }
+}
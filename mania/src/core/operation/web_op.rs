@@ -0,0 +1,776 @@
+use crate::core::business::BusinessHandle;
+use crate::core::event::downcast_mut_major_event;
+use crate::core::event::system::fetch_cookie::FetchCookieEvent;
+use crate::core::http::{self, HttpClient};
+use crate::entity::group_essence::{GroupEssenceContent, GroupEssenceMessage, GroupEssencePage};
+use crate::entity::group_honor::{
+    CurrentTalkative, GroupHonorList, GroupHonorMember, GroupHonorType,
+};
+use crate::entity::group_notice::{GroupNotice, GroupNoticeImage};
+use crate::{ManiaError, ManiaResult, dda};
+use chrono::{DateTime, Utc};
+use reqwest::header::{CONTENT_TYPE, COOKIE, HeaderMap, HeaderValue};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const QUN_DOMAIN: &str = "qun.qq.com";
+const NOTICE_API: &str = "https://web.qun.qq.com/cgi-bin/announce";
+const ESSENCE_API: &str = "https://qun.qq.com/cgi-bin/group_digest/digest_list";
+const HONOR_API: &str = "https://qun.qq.com/interactive/honorlist";
+const NOTICE_PAGE_SIZE: u32 = 20;
+
+/// The csrf token (bkn / g_tk) of the web APIs
+fn csrf_token(p_skey: &str) -> u32 {
+    let hash = p_skey.bytes().fold(5381u32, |hash, c| {
+        hash.wrapping_add(hash << 5).wrapping_add(c as u32)
+    });
+    hash & 0x7fffffff
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&#10;", "\n")
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn web_error(what: &str, e: impl std::fmt::Debug) -> ManiaError {
+    ManiaError::GenericError(Cow::from(format!("{} failed: {:?}", what, e)))
+}
+
+/// Parse a web API response, failing on a non-zero `ec` (or `retcode` of the newer APIs)
+fn parse_response(what: &str, body: &str) -> ManiaResult<Value> {
+    let value: Value = serde_json::from_str(body).map_err(|e| web_error(what, e))?;
+    let code = ["ec", "retcode"]
+        .into_iter()
+        .find_map(|key| value.get(key).and_then(Value::as_i64))
+        .unwrap_or(0);
+    match code {
+        0 => Ok(value),
+        code => Err(web_error(
+            what,
+            format!(
+                "code {}: {}",
+                code,
+                ["em", "retmsg"]
+                    .into_iter()
+                    .find_map(|key| value.get(key).and_then(Value::as_str))
+                    .unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Numbers of the web APIs come as either numbers or strings
+fn as_u32(value: Option<&Value>) -> u32 {
+    match value {
+        Some(Value::Number(n)) => n.as_u64().unwrap_or_default() as u32,
+        Some(Value::String(s)) => s.parse().unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+fn as_str(value: Option<&Value>) -> String {
+    value
+        .and_then(Value::as_str)
+        .map(unescape_html)
+        .unwrap_or_default()
+}
+
+fn to_dt(value: Option<&Value>) -> DateTime<Utc> {
+    DateTime::from_timestamp(as_u32(value) as i64, 0).unwrap_or_default()
+}
+
+fn parse_notice_image(value: &Value) -> Option<GroupNoticeImage> {
+    Some(GroupNoticeImage {
+        id: value.get("id")?.as_str()?.to_string(),
+        width: as_u32(value.get("w")),
+        height: as_u32(value.get("h")),
+    })
+}
+
+fn parse_notice(group_uin: u32, feed: &Value) -> Option<GroupNotice> {
+    let msg = feed.get("msg");
+    Some(GroupNotice {
+        group_uin,
+        notice_id: feed.get("fid")?.as_str()?.to_string(),
+        sender_uin: as_u32(feed.get("u")),
+        publish_time: to_dt(feed.get("pubt")),
+        text: as_str(msg.and_then(|msg| msg.get("text"))),
+        image: msg
+            .and_then(|msg| msg.get("pics"))
+            .and_then(Value::as_array)
+            .and_then(|pics| pics.first())
+            .and_then(parse_notice_image),
+        pinned: as_u32(feed.get("pinned")) == 1,
+        confirm_required: as_u32(
+            feed.get("settings")
+                .and_then(|settings| settings.get("confirm_required")),
+        ) == 1,
+        read_count: as_u32(feed.get("read_num")),
+    })
+}
+
+fn parse_essence_content(value: &Value) -> GroupEssenceContent {
+    match as_u32(value.get("msg_type")) {
+        1 => GroupEssenceContent::Text(as_str(value.get("text"))),
+        2 => GroupEssenceContent::Face(as_u32(value.get("face_index"))),
+        3 => GroupEssenceContent::Image(as_str(value.get("image_url"))),
+        other => GroupEssenceContent::Unknown(other),
+    }
+}
+
+fn parse_essence(group_uin: u32, value: &Value) -> GroupEssenceMessage {
+    GroupEssenceMessage {
+        group_uin,
+        sequence: as_u32(value.get("msg_seq")),
+        random: as_u32(value.get("msg_random")),
+        sender_uin: as_u32(value.get("sender_uin")),
+        sender_name: as_str(value.get("sender_nick")),
+        send_time: to_dt(value.get("sender_time")),
+        operator_uin: as_u32(value.get("add_digest_uin")),
+        operator_name: as_str(value.get("add_digest_nick")),
+        operate_time: to_dt(value.get("add_digest_time")),
+        content: value
+            .get("msg_content")
+            .and_then(Value::as_array)
+            .map(|content| content.iter().map(parse_essence_content).collect())
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_honor_member(value: &Value) -> GroupHonorMember {
+    GroupHonorMember {
+        uin: as_u32(value.get("uin")),
+        name: as_str(value.get("name").or_else(|| value.get("nick"))),
+        avatar: as_str(value.get("avatar")),
+        description: as_str(value.get("desc")),
+    }
+}
+
+fn parse_essence_page(group_uin: u32, body: &str) -> ManiaResult<GroupEssencePage> {
+    let value = parse_response("Fetch group essence", body)?;
+    let data = value.get("data");
+    Ok(GroupEssencePage {
+        messages: data
+            .and_then(|data| data.get("msg_list"))
+            .and_then(Value::as_array)
+            .map(|list| list.iter().map(|v| parse_essence(group_uin, v)).collect())
+            .unwrap_or_default(),
+        is_end: data
+            .and_then(|data| data.get("is_end"))
+            .and_then(Value::as_bool)
+            .unwrap_or(true),
+    })
+}
+
+/// Parse the honor page, the lists are embedded in it as its initial state
+fn parse_honor_page(
+    group_uin: u32,
+    honor_type: GroupHonorType,
+    body: &str,
+) -> ManiaResult<GroupHonorList> {
+    let state = body
+        .split_once("window.__INITIAL_STATE__=")
+        .and_then(|(_, rest)| rest.split_once("</script>"))
+        .map(|(state, _)| state.trim().trim_end_matches(';'))
+        .ok_or_else(|| web_error("Fetch group honor", "no initial state in page"))?;
+    let value: Value =
+        serde_json::from_str(state).map_err(|e| web_error("Fetch group honor", e))?;
+    let current_talkative = match honor_type {
+        GroupHonorType::Talkative => value
+            .get("currentTalkative")
+            .filter(|current| as_u32(current.get("uin")) != 0)
+            .map(|current| CurrentTalkative {
+                uin: as_u32(current.get("uin")),
+                name: as_str(current.get("nick")),
+                avatar: as_str(current.get("avatar")),
+                day_count: as_u32(current.get("day_count")),
+            }),
+        _ => None,
+    };
+    Ok(GroupHonorList {
+        group_uin,
+        honor_type,
+        current_talkative,
+        members: value
+            .get(honor_type.list_key())
+            .and_then(Value::as_array)
+            .map(|list| list.iter().map(parse_honor_member).collect())
+            .unwrap_or_default(),
+    })
+}
+
+impl BusinessHandle {
+    pub(super) fn web_client(&self) -> &HttpClient {
+        self.context
+            .config
+            .http_client
+            .as_deref()
+            .unwrap_or_else(|| http::client())
+    }
+
+    /// Fetch the p_skey of each domain
+    pub async fn fetch_cookies(
+        self: &Arc<Self>,
+        domains: &[&str],
+    ) -> ManiaResult<HashMap<String, String>> {
+        let mut event = dda!(FetchCookieEvent {
+            domains: domains.iter().map(|domain| domain.to_string()).collect(),
+        });
+        let mut res = self.send_event(&mut event).await?;
+        let event: &mut FetchCookieEvent =
+            downcast_mut_major_event(&mut res).ok_or(ManiaError::InternalEventDowncastError)?;
+        Ok(std::mem::take(&mut event.cookies))
+    }
+
+    /// The p_skey of `domain`, fetched once and then served from the cache
+    async fn web_p_skey(self: &Arc<Self>, domain: &str) -> ManiaResult<String> {
+        if let Some(p_skey) = self.cache.web_cookies.get(domain) {
+            return Ok(p_skey.clone());
+        }
+        let p_skey = self
+            .fetch_cookies(&[domain])
+            .await?
+            .remove(domain)
+            .ok_or_else(|| {
+                ManiaError::GenericError(Cow::from(format!("No p_skey for {}", domain)))
+            })?;
+        self.cache
+            .web_cookies
+            .insert(domain.to_string(), p_skey.clone());
+        Ok(p_skey)
+    }
+
+    /// Run `request` with the credentials of `domain`, the cached p_skey is dropped when the
+    /// request fails so that an expired one is fetched again next time
+    async fn with_web_session<T>(
+        self: &Arc<Self>,
+        domain: &str,
+        request: impl AsyncFnOnce(&WebSession<'_>) -> ManiaResult<T>,
+    ) -> ManiaResult<T> {
+        let p_skey = self.web_p_skey(domain).await?;
+        let uin = **self.context.key_store.uin.load();
+        let session = WebSession::new(self.web_client(), uin, &p_skey)?;
+        let res = request(&session).await;
+        if res.is_err() {
+            self.cache.web_cookies.remove(domain);
+        }
+        res
+    }
+
+    /// Fetch all notices of the group, pinned ones first
+    pub async fn fetch_group_notices(
+        self: &Arc<Self>,
+        group_uin: u32,
+    ) -> ManiaResult<Vec<GroupNotice>> {
+        self.with_web_session(QUN_DOMAIN, async |web| {
+            web.notices(group_uin, NOTICE_PAGE_SIZE).await
+        })
+        .await
+    }
+
+    /// Post a notice to the group, `image` is the content of an image file. Returns the id of
+    /// the new notice
+    pub async fn post_group_notice(
+        self: &Arc<Self>,
+        group_uin: u32,
+        text: &str,
+        image: Option<&[u8]>,
+        pinned: bool,
+        confirm_required: bool,
+    ) -> ManiaResult<String> {
+        self.with_web_session(QUN_DOMAIN, async |web| {
+            let image = match image {
+                Some(image) => Some(web.upload_notice_image(image).await?),
+                None => None,
+            };
+            web.add_notice(group_uin, text, image, pinned, confirm_required)
+                .await
+        })
+        .await
+    }
+
+    pub async fn delete_group_notice(
+        self: &Arc<Self>,
+        group_uin: u32,
+        notice_id: &str,
+    ) -> ManiaResult<()> {
+        self.with_web_session(QUN_DOMAIN, async |web| {
+            web.delete_notice(group_uin, notice_id).await
+        })
+        .await
+    }
+
+    /// Fetch a page of the essence messages, `page` starts from 0
+    pub async fn fetch_group_essence(
+        self: &Arc<Self>,
+        group_uin: u32,
+        page: u32,
+        page_size: u32,
+    ) -> ManiaResult<GroupEssencePage> {
+        self.with_web_session(QUN_DOMAIN, async |web| {
+            web.essence_page(group_uin, page, page_size).await
+        })
+        .await
+    }
+
+    pub async fn fetch_group_honor(
+        self: &Arc<Self>,
+        group_uin: u32,
+        honor_type: GroupHonorType,
+    ) -> ManiaResult<GroupHonorList> {
+        self.with_web_session(QUN_DOMAIN, async |web| {
+            web.honor(group_uin, honor_type).await
+        })
+        .await
+    }
+}
+
+/// Cookies and csrf token (bkn) of a web domain, the web APIs are requested through it
+struct WebSession<'a> {
+    client: &'a HttpClient,
+    headers: HeaderMap,
+    bkn: u32,
+}
+
+impl<'a> WebSession<'a> {
+    fn new(client: &'a HttpClient, uin: u32, p_skey: &str) -> ManiaResult<Self> {
+        let cookie = format!("uin=o{0}; p_uin=o{0}; p_skey={1}", uin, p_skey);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&cookie).map_err(|e| web_error("Build cookie", e))?,
+        );
+        Ok(Self {
+            client,
+            headers,
+            bkn: csrf_token(p_skey),
+        })
+    }
+
+    async fn get(
+        &self,
+        what: &str,
+        url: &str,
+        params: HashMap<String, String>,
+    ) -> ManiaResult<String> {
+        self.client
+            .get_async(url, Some(&params), Some(self.headers.clone()))
+            .await
+            .map_err(|e| web_error(what, e))
+    }
+
+    async fn post_form(
+        &self,
+        what: &str,
+        url: &str,
+        form: HashMap<String, String>,
+    ) -> ManiaResult<Value> {
+        let body = self
+            .client
+            .post_form_async(url, Some(form), Some(self.headers.clone()))
+            .await
+            .map_err(|e| web_error(what, e))?;
+        parse_response(what, &body)
+    }
+
+    /// Fetch notices page by page until a page is not full, or brings no notice not seen yet
+    /// (pinned notices may be repeated on every page)
+    async fn notices(&self, group_uin: u32, page_size: u32) -> ManiaResult<Vec<GroupNotice>> {
+        let mut notices = Vec::new();
+        let mut seen = HashSet::new();
+        for page in 1.. {
+            let params = HashMap::from([
+                ("bkn".to_string(), self.bkn.to_string()),
+                ("qid".to_string(), group_uin.to_string()),
+                ("ft".to_string(), "23".to_string()),
+                ("s".to_string(), "-1".to_string()),
+                ("n".to_string(), page_size.to_string()),
+                ("ni".to_string(), "1".to_string()),
+                ("i".to_string(), page.to_string()),
+                ("log_read".to_string(), "1".to_string()),
+                ("platform".to_string(), "1".to_string()),
+            ]);
+            let body = self
+                .get(
+                    "Fetch group notices",
+                    &format!("{}/get_t_list", NOTICE_API),
+                    params,
+                )
+                .await?;
+            let value = parse_response("Fetch group notices", &body)?;
+            let feeds = value
+                .get("feeds")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len);
+            let before = notices.len();
+            // pinned notices are listed in `inst`, others in `feeds`
+            notices.extend(
+                ["inst", "feeds"]
+                    .into_iter()
+                    .filter_map(|key| value.get(key).and_then(Value::as_array))
+                    .flatten()
+                    .filter_map(|feed| parse_notice(group_uin, feed))
+                    .filter(|notice| seen.insert(notice.notice_id.clone())),
+            );
+            if feeds < page_size as usize || notices.len() == before {
+                break;
+            }
+        }
+        Ok(notices)
+    }
+
+    async fn add_notice(
+        &self,
+        group_uin: u32,
+        text: &str,
+        image: Option<GroupNoticeImage>,
+        pinned: bool,
+        confirm_required: bool,
+    ) -> ManiaResult<String> {
+        let settings = serde_json::json!({
+            "is_show_edit_card": 1,
+            "tip_window_type": 1,
+            "confirm_required": confirm_required as u8,
+        });
+        let mut form = HashMap::from([
+            ("qid".to_string(), group_uin.to_string()),
+            ("bkn".to_string(), self.bkn.to_string()),
+            ("text".to_string(), text.to_string()),
+            ("pinned".to_string(), (pinned as u8).to_string()),
+            ("type".to_string(), "1".to_string()),
+            ("settings".to_string(), settings.to_string()),
+        ]);
+        if let Some(image) = image {
+            form.insert("pic".to_string(), image.id);
+            form.insert("imgWidth".to_string(), image.width.to_string());
+            form.insert("imgHeight".to_string(), image.height.to_string());
+        }
+        let value = self
+            .post_form(
+                "Post group notice",
+                &format!("{}/add_qun_notice?bkn={}", NOTICE_API, self.bkn),
+                form,
+            )
+            .await?;
+        value
+            .get("new_fid")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| web_error("Post group notice", "no new_fid in response"))
+    }
+
+    async fn delete_notice(&self, group_uin: u32, notice_id: &str) -> ManiaResult<()> {
+        let form = HashMap::from([
+            ("qid".to_string(), group_uin.to_string()),
+            ("bkn".to_string(), self.bkn.to_string()),
+            ("fid".to_string(), notice_id.to_string()),
+            ("ft".to_string(), "23".to_string()),
+            ("op".to_string(), "1".to_string()),
+        ]);
+        self.post_form(
+            "Delete group notice",
+            &format!("{}/del_feed?bkn={}", NOTICE_API, self.bkn),
+            form,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn essence_page(
+        &self,
+        group_uin: u32,
+        page: u32,
+        page_size: u32,
+    ) -> ManiaResult<GroupEssencePage> {
+        let params = HashMap::from([
+            ("bkn".to_string(), self.bkn.to_string()),
+            ("group_code".to_string(), group_uin.to_string()),
+            ("page_start".to_string(), page.to_string()),
+            ("page_limit".to_string(), page_size.to_string()),
+        ]);
+        let body = self.get("Fetch group essence", ESSENCE_API, params).await?;
+        parse_essence_page(group_uin, &body)
+    }
+
+    async fn honor(
+        &self,
+        group_uin: u32,
+        honor_type: GroupHonorType,
+    ) -> ManiaResult<GroupHonorList> {
+        let params = HashMap::from([
+            ("gc".to_string(), group_uin.to_string()),
+            ("type".to_string(), (honor_type as u32).to_string()),
+        ]);
+        let body = self.get("Fetch group honor", HONOR_API, params).await?;
+        parse_honor_page(group_uin, honor_type, &body)
+    }
+
+    async fn upload_notice_image(&self, image: &[u8]) -> ManiaResult<GroupNoticeImage> {
+        let boundary = format!("----mania{}", uuid::Uuid::new_v4().simple());
+        let mut payload = Vec::with_capacity(image.len() + 512);
+        for (name, value) in [
+            ("bkn", self.bkn.to_string()),
+            ("source", "troopNotice".to_string()),
+            ("m", "0".to_string()),
+        ] {
+            payload.extend_from_slice(
+                format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    boundary, name, value
+                )
+                .as_bytes(),
+            );
+        }
+        payload.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"pic_up\"; filename=\"notice.png\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                boundary
+            )
+            .as_bytes(),
+        );
+        payload.extend_from_slice(image);
+        payload.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        let mut headers = self.headers.clone();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_str(&format!("multipart/form-data; boundary={}", boundary))
+                .map_err(|e| web_error("Upload notice image", e))?,
+        );
+        let body = self
+            .client
+            .post_binary_async(
+                &format!("{}/upload_img", NOTICE_API),
+                &payload,
+                Some(headers),
+            )
+            .await
+            .map_err(|e| web_error("Upload notice image", e))?;
+        let value = parse_response("Upload notice image", &String::from_utf8_lossy(&body))?;
+        // the image info is an html escaped json string
+        let info = value
+            .get("id")
+            .and_then(Value::as_str)
+            .map(unescape_html)
+            .ok_or_else(|| web_error("Upload notice image", "no id in response"))?;
+        let info: Value =
+            serde_json::from_str(&info).map_err(|e| web_error("Upload notice image", e))?;
+        parse_notice_image(&info)
+            .ok_or_else(|| web_error("Upload notice image", "invalid image info"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    // Response bodies of the notice APIs, trimmed to the fields read here
+    const GET_T_LIST: &str = r#"{"ec":0,"em":"","ltsm":1700000200,"srv_code":0,"read_only":0,"role":2,"inst":[{"u":10001,"fid":"0a1b2c3d00000000e8c0a965f1000100","pubt":1700000000,"msg":{"text":"rules &amp; &#39;faq&#39;","text_face":"rules","title":"群公告"},"type":1,"settings":{"is_show_edit_card":1,"tip_window_type":1,"confirm_required":1},"read_num":3,"is_read":0,"pinned":1}],"feeds":[{"u":"10002","fid":"0a1b2c3d00000000e8c0a965f1000200","pubt":"1700000100","msg":{"text":"hello&#10;world","pics":[{"id":"notice-pic","w":"640","h":"480"}]},"type":1,"settings":{"is_show_edit_card":0,"tip_window_type":0,"confirm_required":0},"read_num":"5","is_read":1,"pinned":0}]}"#;
+    const ADD_QUN_NOTICE: &str = r#"{"ec":0,"em":"","id":1,"ltsm":1700000300,"new_fid":"0a1b2c3d00000000e8c0a965f1000300","srv_code":0}"#;
+    const DEL_FEED: &str = r#"{"ec":0,"em":"","ltsm":1700000400,"srv_code":0}"#;
+    // digest_list of a group with three kinds of content and one unknown, on its last page
+    const DIGEST_LIST: &str = r#"{"retcode":0,"retmsg":"ok","data":{"msg_list":[{"group_code":"123456","msg_seq":1024,"msg_random":1145141919,"sender_uin":"10001","sender_nick":"sender &amp; co","sender_time":1700000000,"add_digest_uin":"10002","add_digest_nick":"admin","add_digest_time":1700000100,"msg_content":[{"msg_type":1,"text":"hello"},{"msg_type":2,"face_index":14,"face_text":"/微笑"},{"msg_type":3,"image_url":"https://gchat.qpic.cn/gchatpic_new/0/0-0-ABCDEF/0","image_thumbnail":"https://gchat.qpic.cn/gchatpic_new/0/0-0-ABCDEF/720"},{"msg_type":7}],"can_be_removed":true}],"is_end":true}}"#;
+    // honorlist page of type 1, the state script trimmed to the keys read
+    const HONOR_PAGE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>群荣誉</title></head><body><div id="app"></div><script>window.__INITIAL_STATE__={"groupInfo":{"groupCode":"123456"},"currentTalkative":{"uin":10001,"day_count":3,"avatar":"https://q1.qlogo.cn/g?b=qq&nk=10001&s=100","nick":"talker"},"talkativeList":[{"uin":10001,"avatar":"https://q1.qlogo.cn/g?b=qq&nk=10001&s=100","name":"talker","desc":"连续3天","btnText":"","text":""},{"uin":"10003","avatar":"","name":"runner &#39;up&#39;","desc":"曾经1天"}],"actorList":[],"legendList":[],"emotionList":[],"strongnewbieList":[]};</script><script src="//qun.qq.com/honor.js"></script></body></html>"#;
+    const NO_PERMISSION: &str = r#"{"ec":1,"em":"no permission","ltsm":1700000500,"srv_code":0}"#;
+
+    /// Serve `responses` in order, one connection each, returning the received requests
+    async fn mock_server(responses: Vec<&'static str>) -> (HttpClient, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, content)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(str::to_string)
+                            })
+                            .and_then(|length| length.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if content.len() >= length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                requests.push(String::from_utf8_lossy(&request).to_string());
+            }
+            requests
+        });
+        let client = HttpClient::new().with_origin_override(format!("http://{}", addr));
+        (client, server)
+    }
+
+    /// Whether the query of the request line carries `param`
+    fn has_param(request: &str, param: &str) -> bool {
+        let line = request.lines().next().unwrap_or_default();
+        line.contains(&format!("?{}", param)) || line.contains(&format!("&{}", param))
+    }
+
+    #[test]
+    fn test_csrf_token() {
+        assert_eq!(csrf_token(""), 5381);
+        assert_eq!(csrf_token("abcdefghij-_ABCDEFG1234567890"), 1023718033);
+    }
+
+    #[test]
+    fn test_unescape_html() {
+        assert_eq!(
+            unescape_html("a&#10;b&nbsp;&quot;c&quot; &#39;d&#39; &lt;e&gt; &amp;lt;"),
+            "a\nb \"c\" 'd' <e> &lt;"
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert!(parse_response("test", DEL_FEED).is_ok());
+        let err = parse_response("test", NO_PERMISSION).unwrap_err();
+        assert!(err.to_string().contains("code 1: no permission"));
+        let err = parse_response("test", r#"{"retcode":-1,"retmsg":"bad gc"}"#).unwrap_err();
+        assert!(err.to_string().contains("code -1: bad gc"));
+        assert!(parse_response("test", "<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_notice() {
+        let value: Value = serde_json::from_str(GET_T_LIST).unwrap();
+        let pinned = parse_notice(123456, &value["inst"][0]).unwrap();
+        assert_eq!(pinned.sender_uin, 10001);
+        assert_eq!(pinned.text, "rules & 'faq'");
+        assert!(pinned.pinned && pinned.confirm_required);
+        assert!(pinned.image.is_none());
+        let notice = parse_notice(123456, &value["feeds"][0]).unwrap();
+        assert_eq!(notice.notice_id, "0a1b2c3d00000000e8c0a965f1000200");
+        assert_eq!(notice.sender_uin, 10002);
+        assert_eq!(notice.publish_time.timestamp(), 1700000100);
+        assert_eq!(notice.text, "hello\nworld");
+        assert_eq!(notice.read_count, 5);
+        assert!(!notice.pinned && !notice.confirm_required);
+        let image = notice.image.unwrap();
+        assert_eq!(
+            (image.id.as_str(), image.width, image.height),
+            ("notice-pic", 640, 480)
+        );
+        assert!(parse_notice(123456, &serde_json::json!({"u": 1})).is_none());
+    }
+
+    #[test]
+    fn test_parse_essence_page() {
+        let page = parse_essence_page(123456, DIGEST_LIST).unwrap();
+        assert!(page.is_end);
+        let message = &page.messages[0];
+        assert_eq!((message.sequence, message.random), (1024, 1145141919));
+        assert_eq!(message.sender_uin, 10001);
+        assert_eq!(message.sender_name, "sender & co");
+        assert_eq!(message.send_time.timestamp(), 1700000000);
+        assert_eq!(
+            (message.operator_uin, message.operator_name.as_str()),
+            (10002, "admin")
+        );
+        assert_eq!(message.operate_time.timestamp(), 1700000100);
+        assert!(matches!(
+            &message.content[..],
+            [
+                GroupEssenceContent::Text(text),
+                GroupEssenceContent::Face(14),
+                GroupEssenceContent::Image(url),
+                GroupEssenceContent::Unknown(7),
+            ] if text == "hello" && url.ends_with("ABCDEF/0")
+        ));
+        assert!(parse_essence_page(123456, r#"{"retcode":-1,"retmsg":"not in group"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_honor_page() {
+        let list = parse_honor_page(123456, GroupHonorType::Talkative, HONOR_PAGE).unwrap();
+        let current = list.current_talkative.unwrap();
+        assert_eq!((current.uin, current.day_count), (10001, 3));
+        assert_eq!(current.name, "talker");
+        assert_eq!(list.members.len(), 2);
+        assert_eq!(list.members[0].description, "连续3天");
+        assert_eq!(list.members[1].uin, 10003);
+        assert_eq!(list.members[1].name, "runner 'up'");
+
+        let list = parse_honor_page(123456, GroupHonorType::Legend, HONOR_PAGE).unwrap();
+        assert!(list.current_talkative.is_none() && list.members.is_empty());
+        assert!(parse_honor_page(123456, GroupHonorType::Talkative, "<html></html>").is_err());
+        let broken = HONOR_PAGE.replace("\"talkativeList\":[", "\"talkativeList\":");
+        assert!(parse_honor_page(123456, GroupHonorType::Talkative, &broken).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notice_requests() {
+        let (client, server) =
+            mock_server(vec![GET_T_LIST, ADD_QUN_NOTICE, DEL_FEED, NO_PERMISSION]).await;
+        let web = WebSession::new(&client, 10000, "p_skey").unwrap();
+        let bkn = csrf_token("p_skey");
+
+        assert_eq!(web.notices(123456, 20).await.unwrap().len(), 2);
+        let fid = web
+            .add_notice(123456, "hi", None, true, false)
+            .await
+            .unwrap();
+        assert_eq!(fid, "0a1b2c3d00000000e8c0a965f1000300");
+        web.delete_notice(123456, &fid).await.unwrap();
+        assert!(web.delete_notice(123456, &fid).await.is_err());
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("GET /cgi-bin/announce/get_t_list?"));
+        assert!(requests[0].contains(&format!("bkn={}", bkn)));
+        assert!(has_param(&requests[0], "i=1") && has_param(&requests[0], "n=20"));
+        assert!(requests[0].contains("cookie: uin=o10000; p_uin=o10000; p_skey=p_skey"));
+        assert!(requests[1].starts_with(&format!(
+            "POST /cgi-bin/announce/add_qun_notice?bkn={}",
+            bkn
+        )));
+        assert!(requests[1].contains("pinned=1"));
+        assert!(requests[2].starts_with(&format!("POST /cgi-bin/announce/del_feed?bkn={}", bkn)));
+        assert!(requests[2].contains("fid=0a1b2c3d00000000e8c0a965f1000300"));
+    }
+
+    #[tokio::test]
+    async fn test_notice_paging() {
+        let (client, server) = mock_server(vec![GET_T_LIST, GET_T_LIST]).await;
+        let web = WebSession::new(&client, 10000, "p_skey").unwrap();
+        // the second page brings nothing new, paging stops there
+        let notices = web.notices(123456, 1).await.unwrap();
+        assert_eq!(notices.len(), 2);
+        assert!(notices[0].pinned && !notices[1].pinned);
+        let requests = server.await.unwrap();
+        assert!(has_param(&requests[0], "i=1") && has_param(&requests[1], "i=2"));
+
+        let (client, server) = mock_server(vec![GET_T_LIST]).await;
+        let web = WebSession::new(&client, 10000, "p_skey").unwrap();
+        // a page that is not full is the last one
+        assert_eq!(web.notices(123456, 20).await.unwrap().len(), 2);
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+}
@@ -191,6 +191,18 @@ impl BusinessHandle {
         let _: &InfoSyncEvent =
             downcast_major_event(&res).ok_or(ManiaError::InternalEventDowncastError)?;
         tracing::info!("Online success");
+        let config = &self.context.config;
+        let status = match (&config.custom_status, config.online_status) {
+            (Some(custom), _) => Some(
+                self.set_custom_status(custom.face_id, &custom.wording)
+                    .await,
+            ),
+            (None, Some(status)) => Some(self.set_online_status(status, 0).await),
+            (None, None) => None,
+        };
+        if let Some(Err(e)) = status {
+            tracing::warn!("Failed to set status after online: {:?}", e);
+        }
         tracing::debug!(
             "d2key: {:?}",
             hex::encode(**self.context.key_store.session.d2_key.load())
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BotGroup {
+    pub group_uin: u32,
+    pub group_name: String,
+    pub owner_uid: String,
+    pub member_count: u32,
+    pub max_member: u32,
+    pub create_time: DateTime<Utc>,
+    pub description: String,
+    /// The remark set by the bot, only known after `set_group_remark` as the group list does
+    /// not carry it
+    pub remark: Option<String>,
+    pub avatar: String,
+}
+
+impl BotGroup {
+    pub fn new(
+        group_uin: u32,
+        group_name: String,
+        owner_uid: String,
+        member_count: u32,
+        max_member: u32,
+        create_time: DateTime<Utc>,
+        description: String,
+    ) -> Self {
+        BotGroup {
+            group_uin,
+            group_name,
+            owner_uid,
+            member_count,
+            max_member,
+            create_time,
+            description,
+            remark: None,
+            avatar: format!("https://p.qlogo.cn/gh/{0}/{0}/0", group_uin),
+        }
+    }
+}
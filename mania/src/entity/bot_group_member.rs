@@ -49,3 +49,13 @@ impl BotGroupMember {
         format!("https://q1.qlogo.cn/g?b=qq&nk={}&s=640", self.uin)
     }
 }
+
+/// A member who is currently muted in the group
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MutedGroupMember {
+    pub uin: u32,
+    pub uid: String,
+    pub member_card: Option<String>,
+    pub member_name: Option<String>,
+    pub mute_until: DateTime<Utc>,
+}
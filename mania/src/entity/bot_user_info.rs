@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct BotUserInfo {
     pub uin: u32,
     pub avatar: String,
@@ -20,7 +20,7 @@ pub struct BotUserInfo {
     pub business: Vec<BusinessCustom>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BusinessCustom {
     pub bus_type: u32,
     pub level: u32,
@@ -34,7 +34,7 @@ pub struct BusinessCustomList {
     pub business_lists: Vec<BusinessCustom>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum GenderInfo {
     #[default]
     Unset = 0,
@@ -43,9 +43,34 @@ pub enum GenderInfo {
     Unknown = 255,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct BotStatus {
     pub status_id: u32,
     pub face_id: Option<u32>,
     pub msg: Option<String>,
 }
+
+/// Identifies a user either by uin or by uid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserKey {
+    Uin(u32),
+    Uid(String),
+}
+
+impl From<u32> for UserKey {
+    fn from(uin: u32) -> Self {
+        Self::Uin(uin)
+    }
+}
+
+impl From<&str> for UserKey {
+    fn from(uid: &str) -> Self {
+        Self::Uid(uid.to_string())
+    }
+}
+
+impl From<String> for UserKey {
+    fn from(uid: String) -> Self {
+        Self::Uid(uid)
+    }
+}
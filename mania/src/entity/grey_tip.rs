@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// A templated grey tip shown in the chat timeline, e.g. member joins, avatar changes,
+/// red packets and pokes
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GreyTip {
+    pub busi_type: u64,
+    pub busi_id: u64,
+    pub template_id: u64,
+    /// Raw template, may contain `{key}` placeholders filled by `params`
+    pub content: String,
+    pub params: Vec<(String, String)>,
+}
+
+impl GreyTip {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The template with every known placeholder substituted
+    pub fn text(&self) -> String {
+        self.params
+            .iter()
+            .fold(self.content.clone(), |text, (key, value)| {
+                text.replace(&format!("{{{}}}", key), value)
+            })
+    }
+}
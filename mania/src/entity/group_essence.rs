@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum GroupEssenceContent {
+    Text(String),
+    Face(u32),
+    /// The url of the image
+    Image(String),
+    Unknown(u32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GroupEssenceMessage {
+    pub group_uin: u32,
+    pub sequence: u32,
+    pub random: u32,
+    pub sender_uin: u32,
+    pub sender_name: String,
+    pub send_time: DateTime<Utc>,
+    pub operator_uin: u32,
+    pub operator_name: String,
+    pub operate_time: DateTime<Utc>,
+    pub content: Vec<GroupEssenceContent>,
+}
+
+/// A page of the essence message list, `is_end` is set on the last page
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GroupEssencePage {
+    pub messages: Vec<GroupEssenceMessage>,
+    pub is_end: bool,
+}
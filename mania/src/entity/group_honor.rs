@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum GroupHonorType {
+    Talkative = 1,
+    Performer = 2,
+    Legend = 3,
+    StrongNewbie = 5,
+    Emotion = 6,
+}
+
+impl GroupHonorType {
+    /// The key of the member list in the honor page state
+    pub(crate) fn list_key(self) -> &'static str {
+        match self {
+            GroupHonorType::Talkative => "talkativeList",
+            GroupHonorType::Performer => "actorList",
+            GroupHonorType::Legend => "legendList",
+            GroupHonorType::StrongNewbie => "strongnewbieList",
+            GroupHonorType::Emotion => "emotionList",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GroupHonorMember {
+    pub uin: u32,
+    pub name: String,
+    pub avatar: String,
+    pub description: String,
+}
+
+/// The current dragon king of the group
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CurrentTalkative {
+    pub uin: u32,
+    pub name: String,
+    pub avatar: String,
+    pub day_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GroupHonorList {
+    pub group_uin: u32,
+    pub honor_type: GroupHonorType,
+    /// Only available for `GroupHonorType::Talkative`
+    pub current_talkative: Option<CurrentTalkative>,
+    pub members: Vec<GroupHonorMember>,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GroupNoticeImage {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GroupNoticeImage {
+    pub fn url(&self) -> String {
+        format!("https://gdynamic.qpic.cn/gdynamic/{}/628", self.id)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GroupNotice {
+    pub group_uin: u32,
+    pub notice_id: String,
+    pub sender_uin: u32,
+    pub publish_time: DateTime<Utc>,
+    pub text: String,
+    pub image: Option<GroupNoticeImage>,
+    pub pinned: bool,
+    pub confirm_required: bool,
+    pub read_count: u32,
+}
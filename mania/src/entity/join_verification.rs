@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// How a friend request or a group join request is verified by the target
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum JoinVerification {
+    /// Accepted without any verification
+    AllowAll,
+    /// The request message is reviewed by the target
+    NeedVerify,
+    /// No request is accepted
+    RejectAll,
+    /// The question must be answered correctly, then accepted without review
+    AnswerQuestion(String),
+    /// The answer to the question is reviewed by the target
+    QuestionAndVerify(String),
+}
+
+impl JoinVerification {
+    pub(crate) fn from_friend_setting(setting: u32, question: String) -> Self {
+        match setting {
+            0 => Self::AllowAll,
+            2 => Self::RejectAll,
+            3 => Self::AnswerQuestion(question),
+            4 => Self::QuestionAndVerify(question),
+            _ => Self::NeedVerify,
+        }
+    }
+
+    pub(crate) fn from_group_option(option: u32, question: String) -> Self {
+        match option {
+            1 => Self::AllowAll,
+            3 => Self::RejectAll,
+            4 => Self::AnswerQuestion(question),
+            5 => Self::QuestionAndVerify(question),
+            _ => Self::NeedVerify,
+        }
+    }
+
+    /// The question to answer, if any
+    pub fn question(&self) -> Option<&str> {
+        match self {
+            Self::AnswerQuestion(question) | Self::QuestionAndVerify(question) => Some(question),
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,12 @@
 pub mod bot_friend;
+pub mod bot_group;
 pub mod bot_group_member;
 pub mod bot_group_request;
 pub mod bot_user_info;
+pub mod grey_tip;
+pub mod group_essence;
+pub mod group_honor;
+pub mod group_notice;
+pub mod join_verification;
+pub mod online_status;
 pub mod sys_face;
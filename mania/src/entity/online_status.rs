@@ -0,0 +1,33 @@
+use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// The `Status` of the bot shown to friends
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, TryFromPrimitive)]
+#[repr(u32)]
+pub enum OnlineStatus {
+    #[default]
+    Online = 10,
+    Away = 30,
+    Invisible = 40,
+    Busy = 50,
+    QMe = 60,
+    DoNotDisturb = 70,
+}
+
+/// A custom status with a face and a wording, shown as `Online` with ext status 2000
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CustomStatus {
+    pub face_id: u32,
+    pub wording: String,
+}
+
+impl CustomStatus {
+    pub const EXT_STATUS: u32 = 2000;
+
+    pub fn new(face_id: u32, wording: impl Into<String>) -> Self {
+        Self {
+            face_id,
+            wording: wording.into(),
+        }
+    }
+}
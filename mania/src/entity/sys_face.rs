@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SysFaceEntry {
     pub q_sid: String,
     pub q_des: Option<String>,
@@ -46,6 +46,13 @@ impl SysFaceEntry {
     }
 }
 
+impl SysFaceEntry {
+    /// Whether the face is a super (animated) face, which is sent as a large face
+    pub fn is_large_face(&self) -> bool {
+        self.ani_sticker_type.is_some() && self.ani_sticker_id.is_some()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SysFacePackEntry {
     pub emoji_pack_name: String,
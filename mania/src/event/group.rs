@@ -1,5 +1,6 @@
 pub mod group_admin_changed;
 pub mod group_essence;
+pub mod group_grey_tip;
 pub mod group_invitation;
 pub mod group_invitation_request;
 pub mod group_join_request;
@@ -22,6 +23,7 @@ pub mod group_todo;
 pub enum GroupEvent {
     GroupAdminChanged(group_admin_changed::GroupAdminChangedEvent),
     GroupEssence(group_essence::GroupEssenceEvent),
+    GroupGreyTip(group_grey_tip::GroupGreyTipEvent),
     GroupInvitation(group_invitation::GroupInvitationEvent),
     GroupInvitationRequest(group_invitation_request::GroupInvitationRequestEvent),
     GroupJoinRequest(group_join_request::GroupJoinRequestEvent),
@@ -0,0 +1,8 @@
+use crate::entity::grey_tip::GreyTip;
+pub use mania_macros::ManiaEvent;
+
+#[derive(ManiaEvent)]
+pub struct GroupGreyTipEvent {
+    pub group_uin: u32,
+    pub grey_tip: GreyTip,
+}
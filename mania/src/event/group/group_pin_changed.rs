@@ -1,12 +1,13 @@
 pub use mania_macros::ManiaEvent;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ChatType {
     Friend,
     Group,
     Service,
 }
 
+/// Pin state of a chat changed, sent on the group channel for every [`ChatType`]
 #[derive(ManiaEvent)]
 pub struct PinChangedEvent {
     pub chat_type: ChatType,
@@ -1,7 +1,18 @@
 pub use mania_macros::ManiaEvent;
 
+/// What was done to the todo of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupTodoAction {
+    Set,
+    Complete,
+    Remove,
+    /// Pushed by the server, whose notice does not tell what was done
+    Unknown,
+}
+
 #[derive(ManiaEvent)]
 pub struct GroupTodoEvent {
     pub group_uin: u32,
     pub operator_uin: u32,
+    pub action: GroupTodoAction,
 }
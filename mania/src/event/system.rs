@@ -1,6 +1,7 @@
 pub mod bot_offline;
 pub mod bot_online;
 pub mod bot_rename;
+pub mod self_message;
 pub mod temp_message;
 
 #[derive(Debug)]
@@ -10,4 +11,5 @@ pub enum SystemEvent {
     BotOfflineEvent(bot_offline::BotOfflineEvent),
     TempMessageEvent(temp_message::TempMessageEvent), // FIXME: clippy warn: at least 320 bytes
     BotRenameEvent(bot_rename::BotRenameEvent),
+    SelfMessageEvent(self_message::SelfMessageEvent),
 }
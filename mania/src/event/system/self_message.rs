@@ -0,0 +1,9 @@
+use crate::message::chain::MessageChain;
+pub use mania_macros::ManiaEvent;
+
+/// A message sent by the bot itself, from mania or another device, see
+/// `ClientConfig::self_message_filter`
+#[derive(ManiaEvent)]
+pub struct SelfMessageEvent {
+    pub chain: Box<MessageChain>,
+}
@@ -15,10 +15,13 @@ pub use crate::core::cache::CacheMode;
 use crate::core::context::Protocol;
 pub use crate::core::context::{AppInfo, Context, DeviceInfo};
 pub use crate::core::error::{ManiaError, ManiaResult};
+pub use crate::core::http::HttpClient;
 pub use crate::core::key_store::KeyStore;
 use crate::core::session::Session;
 use crate::core::sign::{SignProvider, default_sign_provider};
 use crate::entity::bot_group_member::FetchGroupMemberStrategy;
+use crate::entity::online_status::{CustomStatus, OnlineStatus};
+use crate::message::filter::SelfMessageFilter;
 use std::env;
 use std::sync::Arc;
 
@@ -44,6 +47,23 @@ pub struct ClientConfig {
     /// Setting it to `Simple` can avoid fetching all group members at the cost of losing some fields
     /// See `BotGroupMember` for more information
     pub fetch_group_member_strategy: FetchGroupMemberStrategy,
+    /// How to deliver messages sent by the bot itself (including from other devices)
+    pub self_message_filter: SelfMessageFilter,
+    /// Report every received message as read, keeps the unread count of other devices at zero
+    pub auto_mark_read: bool,
+    /// The status to set after online, keep the server side status if `None`
+    pub online_status: Option<OnlineStatus>,
+    /// The custom status to set after online, takes precedence over `online_status`
+    pub custom_status: Option<CustomStatus>,
+    /// The HTTP client for web APIs (e.g. group notices), the shared client is used if `None`
+    pub http_client: Option<Arc<HttpClient>>,
+    /// Outgoing messages are uploaded and sent as a long message once their packet exceeds
+    /// this many bytes. What is measured is the whole `MessageSvc.PbSendMsg` payload, routing
+    /// and content heads included, after resources have been uploaded, the SSO framing around
+    /// it is not counted. Neither the server nor Lagrange.Core documents a limit, 4500 is a
+    /// conservative guess rather than a measured one, lower it if large messages still fail
+    /// to send
+    pub max_message_size: usize,
 }
 
 impl Default for ClientConfig {
@@ -58,6 +78,12 @@ impl Default for ClientConfig {
             highway_concurrency: 4,
             cache_mode: CacheMode::Half,
             fetch_group_member_strategy: FetchGroupMemberStrategy::Simple,
+            self_message_filter: SelfMessageFilter::Tag,
+            auto_mark_read: false,
+            online_status: None,
+            custom_status: None,
+            http_client: None,
+            max_message_size: 4500,
         }
     }
 }
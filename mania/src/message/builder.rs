@@ -1,11 +1,24 @@
 use crate::core::highway::{AsyncPureStream, AsyncPureStreamTrait};
 use crate::dda;
-use crate::message::chain::MessageChain;
+use crate::message::chain::{MessageChain, MessageId};
+use crate::message::codec::{self, CqCodeError};
 use crate::message::entity::Entity;
+use crate::message::entity::bounce_face::BounceFaceEntity;
+use crate::message::entity::face::FaceEntity;
 use crate::message::entity::image::ImageEntity;
+use crate::message::entity::json::JsonEntity;
+use crate::message::entity::keyboard::{KeyboardButton, KeyboardEntity};
+use crate::message::entity::light_app::LightAppEntity;
+use crate::message::entity::markdown::MarkdownEntity;
+use crate::message::entity::mention::MentionEntity;
+use crate::message::entity::multi_msg::MultiMsgEntity;
+use crate::message::entity::poke::PokeEntity;
 use crate::message::entity::record::RecordEntity;
+use crate::message::entity::reply::ReplyEntity;
+use crate::message::entity::special_poke::SpecialPokeEntity;
 use crate::message::entity::text::TextEntity;
 use crate::message::entity::video::VideoEntity;
+use crate::message::entity::xml::XmlEntity;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -27,6 +40,47 @@ impl MessageChainBuilder {
         }
     }
 
+    /// Start a chain for the group temporary session with `member_uin` of `group_uin`
+    pub fn temp(group_uin: u32, member_uin: u32) -> Self {
+        Self {
+            chains: MessageChain::temp(group_uin, member_uin),
+        }
+    }
+
+    /// Start a node for [`MessageChainBuilder::forward`], shown as sent by `sender_name`
+    pub fn fake(sender_uin: u32, sender_name: &str) -> Self {
+        Self {
+            chains: MessageChain::fake(sender_uin, sender_name),
+        }
+    }
+
+    pub fn reply(&mut self, chain: &MessageChain) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Reply(ReplyEntity::from_chain(chain)));
+        self
+    }
+
+    /// Mention a member, the uid and display name (group card or nickname) are resolved
+    /// from the cache when the message is sent
+    pub fn mention(&mut self, uin: u32) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Mention(dda!(MentionEntity { uin })));
+        self
+    }
+
+    /// Mention all members of the group, the protocol marks a mention of everyone with uin 0
+    /// (`MentionExtra` type 1 instead of 2, see [`MentionEntity`]), so `uin` is left unset
+    pub fn mention_all(&mut self) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Mention(dda!(MentionEntity {
+                name: Some("@全体成员".to_string()),
+            })));
+        self
+    }
+
     pub fn text(&mut self, content: &str) -> &mut Self {
         self.chains.entities.push(Entity::Text(TextEntity {
             text: content.to_string(),
@@ -34,6 +88,101 @@ impl MessageChainBuilder {
         self
     }
 
+    /// Append the entities parsed from a CQ code string, see [`codec::decode`]
+    pub fn cq_code(&mut self, input: &str) -> Result<&mut Self, CqCodeError> {
+        self.chains.entities.extend(codec::decode(input)?.entities);
+        Ok(self)
+    }
+
+    /// Like [`Self::cq_code`], with the messages quoted by `reply` codes looked up through
+    /// `resolve_reply`, see [`codec::decode_with`]
+    pub fn cq_code_with(
+        &mut self,
+        input: &str,
+        resolve_reply: impl FnMut(MessageId) -> Option<ReplyEntity>,
+    ) -> Result<&mut Self, CqCodeError> {
+        self.chains
+            .entities
+            .extend(codec::decode_with(input, resolve_reply)?.entities);
+        Ok(self)
+    }
+
+    /// Add a system face, whether it is sent as a large face is resolved from the
+    /// system face metadata when the message is sent
+    pub fn face(&mut self, face_id: u16) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Face(dda!(FaceEntity { face_id })));
+        self
+    }
+
+    pub fn bounce_face(&mut self, face_id: u32, count: u32, name: &str) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::BounceFace(BounceFaceEntity {
+                face_id,
+                count,
+                name: name.to_string(),
+            }));
+        self
+    }
+
+    pub fn poke(&mut self, poke_type: u32, strength: u32) -> &mut Self {
+        self.chains.entities.push(Entity::Poke(PokeEntity {
+            poke_type,
+            strength,
+        }));
+        self
+    }
+
+    pub fn special_poke(&mut self, id: u32, name: &str, strength: u32) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::SpecialPoke(SpecialPokeEntity {
+                id,
+                name: name.to_string(),
+                strength,
+            }));
+        self
+    }
+
+    pub fn markdown(&mut self, content: &str) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Markdown(MarkdownEntity::new(content)));
+        self
+    }
+
+    pub fn keyboard(&mut self, rows: Vec<Vec<KeyboardButton>>) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::Keyboard(KeyboardEntity::new(rows)));
+        self
+    }
+
+    pub fn json(&mut self, json: &str) -> &mut Self {
+        self.chains.entities.push(Entity::Json(dda!(JsonEntity {
+            json: json.to_string(),
+        })));
+        self
+    }
+
+    pub fn xml(&mut self, xml: &str, service_id: i32) -> &mut Self {
+        self.chains.entities.push(Entity::Xml(XmlEntity {
+            xml: xml.to_string(),
+            service_id,
+        }));
+        self
+    }
+
+    pub fn light_app(&mut self, app_name: &str, payload: &str) -> &mut Self {
+        self.chains.entities.push(Entity::LightApp(LightAppEntity {
+            app_name: app_name.to_string(),
+            payload: payload.to_string(),
+        }));
+        self
+    }
+
     pub fn image(&mut self, image_path: &str) -> &mut Self {
         self.chains.entities.push(Entity::Image(dda!(ImageEntity {
             file_path: Some(image_path.to_string()),
@@ -55,6 +204,13 @@ impl MessageChainBuilder {
         self
     }
 
+    /// Push an image as a sticker (`sub_type = 1`), e.g. one from `download_favorite_sticker`
+    pub fn sticker(&mut self, mut sticker: ImageEntity) -> &mut Self {
+        sticker.sub_type = 1;
+        self.chains.entities.push(Entity::Image(sticker));
+        self
+    }
+
     pub fn video(&mut self, video_path: &str, video_length: i32) -> &mut Self {
         self.chains.entities.push(Entity::Video(dda!(VideoEntity {
             video_path: Some(video_path.to_string()),
@@ -128,6 +284,13 @@ impl MessageChainBuilder {
         self
     }
 
+    pub fn forward(&mut self, chains: Vec<MessageChain>) -> &mut Self {
+        self.chains
+            .entities
+            .push(Entity::MultiMsg(MultiMsgEntity::new(chains)));
+        self
+    }
+
     pub fn build(&mut self) -> MessageChain {
         std::mem::take(&mut self.chains)
     }
@@ -18,7 +18,7 @@ enum MessageTag {
 pub enum MessageType {
     Friend(FriendMessageUniqueElem),
     Group(GroupMessageUniqueElem),
-    Temp,
+    Temp(TempMessageUniqueElem),
     #[default]
     None,
 }
@@ -35,6 +35,12 @@ pub struct FriendMessageUniqueElem {
     pub client_sequence: ClientSequence,
 }
 
+/// Group temporary session, `group_uin` is the group the session was started from
+#[derive(Debug, Default)]
+pub struct TempMessageUniqueElem {
+    pub group_uin: u32,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
 pub struct MessageId(pub u64);
 
@@ -65,6 +71,8 @@ pub struct MessageChain {
     pub sequence: u32,
     pub(crate) elements: Vec<Elem>,
     pub entities: Vec<Entity>,
+    /// Whether the message was sent by the bot itself, e.g. from another device
+    pub is_self: bool,
 }
 
 /// For debugging output, console, and log display
@@ -77,7 +85,7 @@ impl Debug for MessageChain {
                 "[MessageChain({} -> {})] ",
                 self.friend_uin, group_elem.group_uin
             ),
-            MessageType::Friend(_) | MessageType::Temp => {
+            MessageType::Friend(_) | MessageType::Temp(_) => {
                 format!("[MessageChain({})] ", self.friend_uin)
             }
             MessageType::None => "[MessageChain(Empty)] ".to_string(),
@@ -118,9 +126,30 @@ impl MessageChain {
         })
     }
 
+    /// A node of a multi message (merged forward) bundle, displayed as sent by `sender_name`
+    pub(crate) fn fake(sender_uin: u32, sender_name: &str) -> Self {
+        dda!(Self {
+            typ: MessageType::Friend(dda!(FriendMessageUniqueElem {
+                friend_info: Some(dda!(BotFriend {
+                    uin: sender_uin,
+                    nickname: sender_name.to_string(),
+                })),
+            })),
+            friend_uin: sender_uin,
+            time: Utc::now(),
+        })
+    }
+
     pub(crate) fn group(group_uin: u32) -> Self {
         dda!(Self {
             typ: MessageType::Group(dda!(GroupMessageUniqueElem { group_uin })),
         })
     }
+
+    pub(crate) fn temp(group_uin: u32, member_uin: u32) -> Self {
+        dda!(Self {
+            typ: MessageType::Temp(TempMessageUniqueElem { group_uin }),
+            friend_uin: member_uin,
+        })
+    }
 }
@@ -0,0 +1,317 @@
+//! CQ code (as used by go-cqhttp / OneBot v11) support for message chains
+//!
+//! Supported codes are `at`, `face`, `image`, `record`, `video` and `reply`, plain text is kept
+//! as [`Entity::Text`]. Media only accept local paths (optionally prefixed by `file://`).
+//!
+//! As in go-cqhttp, the `id` of a `reply` code is a message id, the quoted message has to be
+//! looked up by the caller through [`decode_with`].
+
+use crate::dda;
+use crate::message::chain::{MessageChain, MessageId};
+use crate::message::entity::Entity;
+use crate::message::entity::face::FaceEntity;
+use crate::message::entity::image::ImageEntity;
+use crate::message::entity::mention::MentionEntity;
+use crate::message::entity::record::RecordEntity;
+use crate::message::entity::reply::ReplyEntity;
+use crate::message::entity::text::TextEntity;
+use crate::message::entity::video::VideoEntity;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CqCodeError {
+    #[error("Unclosed CQ code starting at byte {0}")]
+    Unclosed(usize),
+    #[error("Unsupported CQ code type: {0}")]
+    UnsupportedType(String),
+    #[error("Missing parameter `{1}` in CQ code {0}")]
+    MissingParam(String, &'static str),
+    #[error("Invalid parameter `{1}` in CQ code {0}: {2}")]
+    InvalidParam(String, &'static str, String),
+    #[error("Malformed parameter `{1}` in CQ code {0}, expected `key=value`")]
+    MalformedParam(String, String),
+    #[error("Replied message {0} could not be resolved")]
+    UnresolvedReply(u64),
+}
+
+/// Parse a CQ code string into a message chain without a target, `reply` codes are rejected
+/// with [`CqCodeError::UnresolvedReply`] since there is no message to quote, see [`decode_with`]
+pub fn decode(input: &str) -> Result<MessageChain, CqCodeError> {
+    decode_with(input, |_| None)
+}
+
+/// Like [`decode`], `resolve_reply` looks up the message quoted by a `reply` code by its
+/// message id, e.g. `|id| store.get(&id).map(ReplyEntity::from_chain)`
+pub fn decode_with(
+    input: &str,
+    mut resolve_reply: impl FnMut(MessageId) -> Option<ReplyEntity>,
+) -> Result<MessageChain, CqCodeError> {
+    let mut chain = MessageChain::default();
+    let mut offset = 0;
+    while let Some(start) = input[offset..].find("[CQ:").map(|i| i + offset) {
+        push_text(&mut chain.entities, &input[offset..start]);
+        let end = input[start..]
+            .find(']')
+            .map(|i| i + start)
+            .ok_or(CqCodeError::Unclosed(start))?;
+        chain
+            .entities
+            .push(decode_code(&input[start + 4..end], &mut resolve_reply)?);
+        offset = end + 1;
+    }
+    push_text(&mut chain.entities, &input[offset..]);
+    Ok(chain)
+}
+
+/// Render entities as a CQ code string, entities without a CQ code are rendered as their
+/// text preview
+pub fn encode(entities: &[Entity]) -> String {
+    entities.iter().map(encode_entity).collect()
+}
+
+fn push_text(entities: &mut Vec<Entity>, text: &str) {
+    if !text.is_empty() {
+        entities.push(Entity::Text(TextEntity {
+            text: unescape(text),
+        }));
+    }
+}
+
+fn decode_code(
+    body: &str,
+    resolve_reply: &mut impl FnMut(MessageId) -> Option<ReplyEntity>,
+) -> Result<Entity, CqCodeError> {
+    let mut parts = body.split(',');
+    let typ = parts.next().unwrap_or_default().trim().to_string();
+    let params: HashMap<&str, String> = parts
+        .map(|part| {
+            part.split_once('=')
+                .map(|(key, value)| (key.trim(), unescape(value)))
+                .ok_or_else(|| CqCodeError::MalformedParam(typ.clone(), part.to_string()))
+        })
+        .collect::<Result<_, _>>()?;
+    let param = |key: &'static str| {
+        params
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| CqCodeError::MissingParam(typ.clone(), key))
+    };
+    let number = |key: &'static str| {
+        param(key)?
+            .parse::<u32>()
+            .map_err(|e| CqCodeError::InvalidParam(typ.clone(), key, e.to_string()))
+    };
+    let path = |key: &'static str| {
+        let file = param(key)?;
+        if file.starts_with("http://")
+            || file.starts_with("https://")
+            || file.starts_with("base64://")
+        {
+            return Err(CqCodeError::InvalidParam(
+                typ.clone(),
+                key,
+                "only local files are supported".to_string(),
+            ));
+        }
+        Ok(file.strip_prefix("file://").unwrap_or(file).to_string())
+    };
+    Ok(match typ.as_str() {
+        "text" => Entity::Text(TextEntity {
+            text: param("text")?.to_string(),
+        }),
+        "at" => match param("qq")? {
+            "all" => Entity::Mention(dda!(MentionEntity {
+                name: Some("@全体成员".to_string()),
+            })),
+            _ => Entity::Mention(dda!(MentionEntity {
+                uin: number("qq")?,
+                name: params.get("name").map(|name| format!("@{}", name)),
+            })),
+        },
+        "face" => Entity::Face(dda!(FaceEntity {
+            face_id: number("id")? as u16,
+        })),
+        "image" => Entity::Image(dda!(ImageEntity {
+            file_path: Some(path("file")?),
+        })),
+        "record" => Entity::Record(dda!(RecordEntity {
+            file_path: Some(path("file")?),
+        })),
+        "video" => Entity::Video(dda!(VideoEntity {
+            video_path: Some(path("file")?),
+            video_thumb_path: params
+                .contains_key("cover")
+                .then(|| path("cover"))
+                .transpose()?,
+        })),
+        "reply" => {
+            let id = param("id")?
+                .parse::<u64>()
+                .map_err(|e| CqCodeError::InvalidParam(typ.clone(), "id", e.to_string()))?;
+            Entity::Reply(resolve_reply(MessageId(id)).ok_or(CqCodeError::UnresolvedReply(id))?)
+        }
+        _ => return Err(CqCodeError::UnsupportedType(typ)),
+    })
+}
+
+fn encode_entity(entity: &Entity) -> String {
+    match entity {
+        Entity::Text(text) => escape(&text.text, false),
+        Entity::Mention(mention) if mention.uin == 0 => "[CQ:at,qq=all]".to_string(),
+        Entity::Mention(mention) => format!("[CQ:at,qq={}]", mention.uin),
+        Entity::Face(face) => format!("[CQ:face,id={}]", face.face_id),
+        Entity::Image(image) => encode_media(
+            "image",
+            image
+                .file_path
+                .clone()
+                .unwrap_or_else(|| hex::encode(&image.md5)),
+            &image.url,
+        ),
+        Entity::Record(record) => encode_media(
+            "record",
+            record
+                .file_path
+                .clone()
+                .unwrap_or_else(|| record.audio_name.clone()),
+            &record.audio_url,
+        ),
+        Entity::Video(video) => encode_media(
+            "video",
+            video
+                .video_path
+                .clone()
+                .unwrap_or_else(|| video.file_name.clone()),
+            &video.video_url,
+        ),
+        Entity::Reply(reply) => format!("[CQ:reply,id={}]", reply.message_id.0),
+        other => escape(&other.to_string(), false),
+    }
+}
+
+fn encode_media(typ: &str, file: String, url: &str) -> String {
+    let mut code = format!("[CQ:{},file={}", typ, escape(&file, true));
+    if !url.is_empty() {
+        code.push_str(&format!(",url={}", escape(url, true)));
+    }
+    code.push(']');
+    code
+}
+
+fn escape(text: &str, is_param: bool) -> String {
+    let text = text
+        .replace('&', "&amp;")
+        .replace('[', "&#91;")
+        .replace(']', "&#93;");
+    if is_param {
+        text.replace(',', "&#44;")
+    } else {
+        text
+    }
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&#44;", ",")
+        .replace("&#91;", "[")
+        .replace("&#93;", "]")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::chain::MessageType;
+
+    fn quoted(id: MessageId) -> Option<ReplyEntity> {
+        (id.0 == 42).then(|| {
+            dda!(ReplyEntity {
+                message_id: id,
+                sequence: 7,
+                sender_uin: 10001,
+            })
+        })
+    }
+
+    #[test]
+    fn test_cq_code_round_trip() {
+        let input = "hi [CQ:at,qq=123] &#91;x&#93;[CQ:face,id=14][CQ:image,file=file:///tmp/a&#44;b.png][CQ:reply,id=42]";
+        let chain = decode_with(input, quoted).unwrap();
+        assert!(matches!(chain.typ, MessageType::None));
+        let entities = &chain.entities;
+        assert_eq!(entities.len(), 6);
+        assert!(matches!(&entities[1], Entity::Mention(m) if m.uin == 123));
+        assert!(matches!(&entities[2], Entity::Text(t) if t.text == " [x]"));
+        assert!(
+            matches!(&entities[4], Entity::Image(i) if i.file_path.as_deref() == Some("/tmp/a,b.png"))
+        );
+        assert!(
+            matches!(&entities[5], Entity::Reply(r) if r.message_id.0 == 42 && r.sender_uin == 10001)
+        );
+        assert_eq!(
+            encode(entities),
+            "hi [CQ:at,qq=123] &#91;x&#93;[CQ:face,id=14][CQ:image,file=/tmp/a&#44;b.png][CQ:reply,id=42]"
+        );
+    }
+
+    #[test]
+    fn test_cq_code_escaping() {
+        let text = "a&b [c] d,e &amp; &#91;";
+        let chain = decode(&escape(text, false)).unwrap();
+        assert!(matches!(&chain.entities[..], [Entity::Text(t)] if t.text == text));
+        assert_eq!(encode(&chain.entities), escape(text, false));
+
+        let path = "/tmp/[a],&b.png";
+        let code = format!("[CQ:record,file={}]", escape(path, true));
+        let chain = decode(&code).unwrap();
+        assert!(
+            matches!(&chain.entities[..], [Entity::Record(r)] if r.file_path.as_deref() == Some(path))
+        );
+        assert_eq!(encode(&chain.entities), code);
+
+        let chain = decode("[CQ:at,qq=all][CQ:text,text=&#91;raw&#93;]").unwrap();
+        assert!(matches!(&chain.entities[0], Entity::Mention(m) if m.uin == 0));
+        assert!(matches!(&chain.entities[1], Entity::Text(t) if t.text == "[raw]"));
+        assert_eq!(encode(&chain.entities), "[CQ:at,qq=all]&#91;raw&#93;");
+    }
+
+    #[test]
+    fn test_cq_code_rejected() {
+        assert!(matches!(
+            decode("[CQ:share,url=https://a]"),
+            Err(CqCodeError::UnsupportedType(typ)) if typ == "share"
+        ));
+        assert!(matches!(
+            decode("[CQ:]"),
+            Err(CqCodeError::UnsupportedType(typ)) if typ.is_empty()
+        ));
+        assert!(matches!(
+            decode("[CQ:image,file=https://a/b.png]"),
+            Err(CqCodeError::InvalidParam(..))
+        ));
+        assert!(matches!(
+            decode("[CQ:at,qq=1"),
+            Err(CqCodeError::Unclosed(0))
+        ));
+        assert!(matches!(
+            decode("ok [CQ:face]"),
+            Err(CqCodeError::MissingParam(_, "id"))
+        ));
+        assert!(matches!(
+            decode("[CQ:face,id=smile]"),
+            Err(CqCodeError::InvalidParam(_, "id", _))
+        ));
+        assert!(matches!(
+            decode("[CQ:face,id]"),
+            Err(CqCodeError::MalformedParam(_, part)) if part == "id"
+        ));
+        assert!(matches!(
+            decode("[CQ:reply,id=42]"),
+            Err(CqCodeError::UnresolvedReply(42))
+        ));
+        assert!(matches!(
+            decode_with("[CQ:reply,id=43]", quoted),
+            Err(CqCodeError::UnresolvedReply(43))
+        ));
+    }
+}
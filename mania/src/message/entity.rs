@@ -1,34 +1,46 @@
+pub mod bounce_face;
 pub mod extra_general_flags;
 pub mod extra_info;
 pub mod face;
 pub mod file;
-pub mod forward;
 pub mod image;
 pub mod json;
+pub mod keyboard;
 pub mod light_app;
 pub mod long_msg;
+pub mod markdown;
 pub mod market_face;
 pub mod mention;
 pub mod multi_msg;
+pub mod poke;
 pub mod record;
+pub mod reply;
+pub mod special_poke;
 pub mod text;
+pub mod unknown;
 pub mod video;
 pub mod xml;
 
+pub use bounce_face::BounceFaceEntity as BounceFace;
 pub use extra_general_flags::ExtraGeneralFlagsEntity as ExtraGeneralFlags;
 pub use extra_info::ExtraInfoEntity as ExtraInfo;
 pub use face::FaceEntity as Face;
 pub use file::FileEntity as File;
-pub use forward::ForwardEntity as Forward;
 pub use image::ImageEntity as Image;
 pub use json::JsonEntity as Json;
+pub use keyboard::KeyboardEntity as Keyboard;
 pub use light_app::LightAppEntity as LightApp;
 pub use long_msg::LongMsgEntity as LongMsg;
+pub use markdown::MarkdownEntity as Markdown;
 pub use market_face::MarketFaceEntity as MarketFace;
 pub use mention::MentionEntity as Mention;
 pub use multi_msg::MultiMsgEntity as MultiMsg;
+pub use poke::PokeEntity as Poke;
 pub use record::RecordEntity as Record;
+pub use reply::ReplyEntity as Reply;
+pub use special_poke::SpecialPokeEntity as SpecialPoke;
 pub use text::TextEntity as Text;
+pub use unknown::UnknownEntity as Unknown;
 pub use video::VideoEntity as Video;
 pub use xml::XmlEntity as Xml;
 
@@ -75,7 +87,7 @@ pub enum Entity {
     Json(json::JsonEntity),
     Image(image::ImageEntity),
     Face(face::FaceEntity),
-    Forward(forward::ForwardEntity),
+    Reply(reply::ReplyEntity),
     MarketFace(market_face::MarketFaceEntity),
     LightApp(light_app::LightAppEntity),
     MultiMsg(multi_msg::MultiMsgEntity),
@@ -84,9 +96,15 @@ pub enum Entity {
     Record(record::RecordEntity),
     Video(video::VideoEntity), // FIXME: clippy warn: at least 800 bytes
     Xml(xml::XmlEntity),
+    Poke(poke::PokeEntity),
+    SpecialPoke(special_poke::SpecialPokeEntity),
+    BounceFace(bounce_face::BounceFaceEntity),
+    Markdown(markdown::MarkdownEntity),
+    Keyboard(keyboard::KeyboardEntity),
     LongMsg(long_msg::LongMsgEntity), // FIXME: clippy warn: at least 344 bytes
     ExtraInfo(extra_info::ExtraInfoEntity),
     ExtraGeneralFlags(extra_general_flags::ExtraGeneralFlagsEntity),
+    Unknown(unknown::UnknownEntity),
 }
 
 macro_rules! impl_entity_show {
@@ -182,7 +200,7 @@ impl_show_pack_all!(
     Json,
     Image,
     Face,
-    Forward,
+    Reply,
     MarketFace,
     LightApp,
     MultiMsg,
@@ -191,21 +209,78 @@ impl_show_pack_all!(
     Record,
     Video,
     Xml,
+    Poke,
+    SpecialPoke,
+    BounceFace,
+    Markdown,
+    Keyboard,
     LongMsg,
     ExtraInfo,
-    ExtraGeneralFlags
+    ExtraGeneralFlags,
+    Unknown
 );
 
 impl_common_entity_unpack!(
-    Text, Json, Image, Face, Forward, MarketFace, LightApp, MultiMsg, Mention, File, Record, Video,
-    Xml, LongMsg
+    Text,
+    Json,
+    Image,
+    Face,
+    Reply,
+    MarketFace,
+    MultiMsg,
+    LightApp,
+    Mention,
+    File,
+    Record,
+    Video,
+    Xml,
+    SpecialPoke,
+    Poke,
+    BounceFace,
+    Markdown,
+    Keyboard,
+    LongMsg
 );
 
 impl_extra_entity_unpack!(ExtraInfo, ExtraGeneralFlags);
 
 impl Entity {
     pub fn from_elems(elems: &[Elem]) -> Vec<Self> {
-        elems.iter().filter_map(Entity::unpack_element).collect()
+        Self::from_raw_elems(elems, &[])
+    }
+
+    /// Like `from_elems`, with `raw` being the elements as received so that unknown ones keep
+    /// the fields `Elem` can not hold. `raw` is ignored unless it matches `elems` one to one
+    pub(crate) fn from_raw_elems(elems: &[Elem], raw: &[Vec<u8>]) -> Vec<Self> {
+        let raw = (raw.len() == elems.len()).then_some(raw);
+        elems
+            .iter()
+            .enumerate()
+            .filter_map(|(i, elem)| {
+                Entity::unpack_element(elem).or_else(|| {
+                    <Unknown as MessageEntity>::unpack_element(elem).map(|unknown| {
+                        Entity::Unknown(match raw {
+                            Some(raw) => {
+                                Unknown::with_raw(unknown.elem, Bytes::from(raw[i].clone()))
+                            }
+                            None => unknown,
+                        })
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Encoded elements of the entity, unknown entities are written back as received
+    pub(crate) fn to_raw_elems(&self, ctx: &Context) -> Vec<Vec<u8>> {
+        match self {
+            Entity::Unknown(unknown) => vec![unknown.raw.to_vec()],
+            _ => self
+                .pack_element(ctx)
+                .iter()
+                .map(prost::Message::encode_to_vec)
+                .collect(),
+        }
     }
 
     pub fn to_elems(&self, ctx: &Context) -> Vec<Elem> {
@@ -0,0 +1,63 @@
+use super::prelude::*;
+
+#[pack_content(false)]
+#[derive(Default)]
+pub struct BounceFaceEntity {
+    pub face_id: u32,
+    pub count: u32,
+    pub name: String,
+}
+
+impl Debug for BounceFaceEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "[BounceFace]: {} FaceId: {} Count: {}",
+            self.name, self.face_id, self.count
+        )
+    }
+}
+
+impl Display for BounceFaceEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[弹射表情]")
+    }
+}
+
+impl MessageEntity for BounceFaceEntity {
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        let extra = dda!(BounceFaceExtra {
+            face_id: self.face_id,
+            count: self.count,
+            summary: self.name.clone(),
+            face: Some(QSmallFaceExtra {
+                face_id: self.face_id,
+                text: self.name.clone(),
+                compat_text: self.name.clone(),
+            }),
+        });
+        vec![dda!(Elem {
+            common_elem: Some(CommonElem {
+                service_type: 23,
+                pb_elem: extra.encode_to_vec(),
+                business_type: 1,
+            }),
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let common = elem.common_elem.as_ref()?;
+        if common.service_type != 23 {
+            return None;
+        }
+        let extra = BounceFaceExtra::decode(&*common.pb_elem).ok()?;
+        Some(Self {
+            face_id: extra
+                .face
+                .as_ref()
+                .map_or(extra.face_id, |face| face.face_id),
+            count: extra.count,
+            name: extra.summary,
+        })
+    }
+}
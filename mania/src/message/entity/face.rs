@@ -24,7 +24,57 @@ impl Display for FaceEntity {
 
 impl MessageEntity for FaceEntity {
     fn pack_element(&self, _: &Context) -> Vec<Elem> {
-        todo!()
+        let entry = self.sys_face_entry.as_ref();
+        let des = entry.and_then(|e| e.q_des.clone()).unwrap_or_default();
+        if self.is_large_face {
+            let ani_sticker_type = entry.and_then(|e| e.ani_sticker_type).unwrap_or(1);
+            let extra = QBigFaceExtra {
+                ani_sticker_pack_id: Some(
+                    entry
+                        .and_then(|e| e.ani_sticker_pack_id)
+                        .unwrap_or(1)
+                        .to_string(),
+                ),
+                ani_sticker_id: Some(
+                    entry
+                        .and_then(|e| e.ani_sticker_id)
+                        .unwrap_or(8)
+                        .to_string(),
+                ),
+                face_id: Some(self.face_id as i32),
+                source_type: Some(1),
+                ani_sticker_type: Some(ani_sticker_type),
+                result_id: Some(String::new()),
+                preview: Some(des),
+                random_type: Some(1),
+            };
+            return vec![dda!(Elem {
+                common_elem: Some(CommonElem {
+                    service_type: 37,
+                    pb_elem: extra.encode_to_vec(),
+                    business_type: ani_sticker_type as u32,
+                }),
+            })];
+        }
+        if self.face_id >= 260 {
+            let extra = QSmallFaceExtra {
+                face_id: self.face_id as u32,
+                text: des.clone(),
+                compat_text: des,
+            };
+            return vec![dda!(Elem {
+                common_elem: Some(CommonElem {
+                    service_type: 33,
+                    pb_elem: extra.encode_to_vec(),
+                    business_type: 1,
+                }),
+            })];
+        }
+        vec![dda!(Elem {
+            face: Some(dda!(Face {
+                index: Some(self.face_id as i32),
+            })),
+        })]
     }
 
     fn unpack_element(elem: &Elem) -> Option<Self> {
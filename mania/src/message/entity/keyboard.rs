@@ -0,0 +1,185 @@
+use super::prelude::*;
+use num_enum::TryFromPrimitive;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, TryFromPrimitive, Serialize, Deserialize)]
+#[repr(i32)]
+pub enum ButtonActionType {
+    /// Open the url in `data`
+    #[default]
+    Jump = 0,
+    /// Send a callback carrying `data` to the keyboard sender. Clicks are reported to the bot
+    /// platform that sent the keyboard, not pushed to this client, so there is no event for them
+    Callback = 1,
+    /// Put `data` into the input box, or send it directly if `enter` is set
+    Command = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, TryFromPrimitive, Serialize, Deserialize)]
+#[repr(i32)]
+pub enum ButtonPermissionType {
+    SpecifiedUsers = 0,
+    Admin = 1,
+    #[default]
+    Everyone = 2,
+    SpecifiedRoles = 3,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyboardButton {
+    pub id: String,
+    pub label: String,
+    pub visited_label: String,
+    pub style: i32,
+    pub action_type: ButtonActionType,
+    pub data: String,
+    pub permission_type: ButtonPermissionType,
+    pub specify_role_ids: Vec<String>,
+    pub specify_user_ids: Vec<String>,
+    pub unsupported_tips: String,
+    pub reply: bool,
+    pub enter: bool,
+}
+
+impl KeyboardButton {
+    pub fn new(
+        id: impl Into<String>,
+        label: impl Into<String>,
+        action_type: ButtonActionType,
+        data: impl Into<String>,
+    ) -> Self {
+        let label = label.into();
+        Self {
+            id: id.into(),
+            visited_label: label.clone(),
+            label,
+            action_type,
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    fn to_proto(&self) -> Button {
+        Button {
+            id: self.id.clone(),
+            render_data: Some(RenderData {
+                label: self.label.clone(),
+                visited_label: self.visited_label.clone(),
+                style: self.style,
+            }),
+            action: Some(Action {
+                r#type: self.action_type as i32,
+                permission: Some(Permission {
+                    r#type: self.permission_type as i32,
+                    specify_role_ids: self.specify_role_ids.clone(),
+                    specify_user_ids: self.specify_user_ids.clone(),
+                }),
+                unsupported_tips: self.unsupported_tips.clone(),
+                data: self.data.clone(),
+                reply: self.reply,
+                enter: self.enter,
+            }),
+        }
+    }
+
+    fn from_proto(button: Button) -> Self {
+        let render = button.render_data.unwrap_or_default();
+        let action = button.action.unwrap_or_default();
+        let permission = action.permission.unwrap_or_default();
+        Self {
+            id: button.id,
+            label: render.label,
+            visited_label: render.visited_label,
+            style: render.style,
+            action_type: ButtonActionType::try_from(action.r#type).unwrap_or_default(),
+            data: action.data,
+            permission_type: ButtonPermissionType::try_from(permission.r#type).unwrap_or_default(),
+            specify_role_ids: permission.specify_role_ids,
+            specify_user_ids: permission.specify_user_ids,
+            unsupported_tips: action.unsupported_tips,
+            reply: action.reply,
+            enter: action.enter,
+        }
+    }
+}
+
+#[pack_content(false)]
+#[derive(Default)]
+pub struct KeyboardEntity {
+    pub rows: Vec<Vec<KeyboardButton>>,
+}
+
+impl KeyboardEntity {
+    pub fn new(rows: Vec<Vec<KeyboardButton>>) -> Self {
+        Self { rows }
+    }
+
+    pub fn buttons(&self) -> impl Iterator<Item = &KeyboardButton> {
+        self.rows.iter().flatten()
+    }
+}
+
+impl Debug for KeyboardEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[Keyboard]: ")?;
+        for (i, row) in self.rows.iter().enumerate() {
+            if i > 0 {
+                write!(f, " / ")?;
+            }
+            let labels: Vec<&str> = row.iter().map(|b| b.label.as_str()).collect();
+            write!(f, "{}", labels.join(" | "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for KeyboardEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[按钮]")
+    }
+}
+
+impl MessageEntity for KeyboardEntity {
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        let extra = ButtonExtra {
+            data: Some(KeyboardData {
+                rows: self
+                    .rows
+                    .iter()
+                    .map(|row| Row {
+                        buttons: row.iter().map(KeyboardButton::to_proto).collect(),
+                    })
+                    .collect(),
+            }),
+        };
+        vec![dda!(Elem {
+            common_elem: Some(CommonElem {
+                service_type: 46,
+                pb_elem: extra.encode_to_vec(),
+                business_type: 1,
+            }),
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let common = elem.common_elem.as_ref()?;
+        if common.service_type != 46 {
+            return None;
+        }
+        let extra = ButtonExtra::decode(&*common.pb_elem).ok()?;
+        Some(Self {
+            rows: extra
+                .data
+                .unwrap_or_default()
+                .rows
+                .into_iter()
+                .map(|row| {
+                    row.buttons
+                        .into_iter()
+                        .map(KeyboardButton::from_proto)
+                        .collect()
+                })
+                .collect(),
+        })
+    }
+}
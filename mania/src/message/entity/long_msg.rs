@@ -26,7 +26,12 @@ impl Display for LongMsgEntity {
 
 impl MessageEntity for LongMsgEntity {
     fn pack_element(&self, _: &Context) -> Vec<Elem> {
-        todo!()
+        vec![dda!(Elem {
+            general_flags: Some(dda!(GeneralFlags {
+                long_text_flag: 1,
+                long_text_res_id: Some(self.res_id.clone()),
+            })),
+        })]
     }
 
     fn unpack_element(elem: &Elem) -> Option<Self> {
@@ -0,0 +1,53 @@
+use super::prelude::*;
+
+#[pack_content(false)]
+#[derive(Default)]
+pub struct MarkdownEntity {
+    pub content: String,
+}
+
+impl MarkdownEntity {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+}
+
+impl Debug for MarkdownEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[Markdown]: {}", self.content)
+    }
+}
+
+impl Display for MarkdownEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[Markdown]")
+    }
+}
+
+impl MessageEntity for MarkdownEntity {
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        vec![dda!(Elem {
+            common_elem: Some(CommonElem {
+                service_type: 45,
+                pb_elem: MarkdownData {
+                    content: self.content.clone(),
+                }
+                .encode_to_vec(),
+                business_type: 1,
+            }),
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let common = elem.common_elem.as_ref()?;
+        if common.service_type != 45 {
+            return None;
+        }
+        let data = MarkdownData::decode(&*common.pb_elem).ok()?;
+        Some(Self {
+            content: data.content,
+        })
+    }
+}
@@ -28,7 +28,30 @@ impl Display for MarketFaceEntity {
 
 impl MessageEntity for MarketFaceEntity {
     fn pack_element(&self, _: &Context) -> Vec<Elem> {
-        todo!()
+        vec![
+            dda!(Elem {
+                market_face: Some(MarketFace {
+                    face_name: Some(self.summary.clone()),
+                    item_type: Some(6),
+                    face_info: Some(1),
+                    face_id: hex::decode(&self.emoji_id).ok(),
+                    tab_id: Some(self.emoji_package_id),
+                    sub_type: Some(3),
+                    key: Some(self.key.clone()),
+                    param: None,
+                    media_type: Some(0),
+                    image_width: Some(300),
+                    image_height: Some(300),
+                    mobile_param: None,
+                    pb_reserve: Some(MarketFacePbReserve { field8: 1 }.encode_to_vec()),
+                }),
+            }),
+            dda!(Elem {
+                text: Some(dda!(Text {
+                    str: Some(self.summary.clone()),
+                })),
+            }),
+        ]
     }
 
     fn unpack_element(elem: &Elem) -> Option<Self> {
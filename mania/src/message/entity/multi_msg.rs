@@ -1,6 +1,8 @@
 use super::prelude::*;
-use crate::message::chain::MessageChain;
+use crate::message::chain::{MessageChain, MessageType};
+use crate::message::entity::light_app::LightAppEntity;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[pack_content(false)]
 #[derive(Default)]
@@ -139,6 +141,71 @@ pub struct MultiSource {
     pub name: Option<String>,
 }
 
+impl MultiMsgEntity {
+    pub fn new(chains: Vec<MessageChain>) -> Self {
+        dda!(Self { chains })
+    }
+
+    fn sender_name(chain: &MessageChain) -> String {
+        match &chain.typ {
+            MessageType::Friend(friend) => friend.friend_info.as_ref().map(|f| f.nickname.clone()),
+            MessageType::Group(group) => group.group_member_info.as_ref().and_then(|member| {
+                member
+                    .member_card
+                    .clone()
+                    .or_else(|| member.member_name.clone())
+            }),
+            _ => None,
+        }
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| chain.friend_uin.to_string())
+    }
+
+    fn light_app(&self) -> MultiMsgLightApp {
+        let is_group = self.chains.first().is_some_and(|chain| chain.is_group());
+        MultiMsgLightApp {
+            app: "com.tencent.multimsg".to_string(),
+            config: Config {
+                autosize: 1,
+                forward: 1,
+                round: 1,
+                type_field: "normal".to_string(),
+                width: 300,
+            },
+            desc: "[聊天记录]".to_string(),
+            extra: serde_json::to_string(&MultiMsgLightAppExtra {
+                filename: Uuid::new_v4().to_string(),
+                sum: self.chains.len() as i32,
+            })
+            .unwrap_or_default(),
+            meta: Meta {
+                detail: Detail {
+                    news: self
+                        .chains
+                        .iter()
+                        .take(4)
+                        .map(|chain| News {
+                            text: format!("{}: {}", Self::sender_name(chain), chain),
+                        })
+                        .collect(),
+                    resid: self.res_id.clone(),
+                    source: if is_group {
+                        "群聊的聊天记录"
+                    } else {
+                        "聊天记录"
+                    }
+                    .to_string(),
+                    summary: format!("查看{}条转发消息", self.chains.len()),
+                    uniseq: Uuid::new_v4().to_string(),
+                },
+            },
+            prompt: "[聊天记录]".to_string(),
+            ver: "0.0.0.5".to_string(),
+            view: "contact".to_string(),
+        }
+    }
+}
+
 impl Debug for MultiMsgEntity {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(
@@ -157,11 +224,33 @@ impl Display for MultiMsgEntity {
 }
 
 impl MessageEntity for MultiMsgEntity {
-    fn pack_element(&self, _: &Context) -> Vec<Elem> {
-        todo!()
+    fn pack_element(&self, ctx: &Context) -> Vec<Elem> {
+        let payload = match serde_json::to_string(&self.light_app()) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize MultiMsgLightApp: {:?}", e);
+                return vec![];
+            }
+        };
+        LightAppEntity {
+            app_name: "com.tencent.multimsg".to_string(),
+            payload,
+        }
+        .pack_element(ctx)
     }
 
     fn unpack_element(elem: &Elem) -> Option<Self> {
+        if let Some(light_app) = elem.light_app_elem.as_ref() {
+            let json = zlib::decompress(light_app.data.get(1..)?)
+                .and_then(|decompressed| String::from_utf8(decompressed).ok())?;
+            let app: MultiMsgLightApp = serde_json::from_str(&json).ok()?;
+            return (app.app == "com.tencent.multimsg").then(|| {
+                dda!(Self {
+                    res_id: app.meta.detail.resid,
+                    detail_str: Some(json),
+                })
+            });
+        }
         match (
             elem.rich_msg.as_ref()?.service_id,
             elem.rich_msg.as_ref()?.template1.as_ref(),
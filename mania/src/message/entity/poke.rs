@@ -0,0 +1,51 @@
+use super::prelude::*;
+
+#[pack_content(false)]
+#[derive(Default)]
+pub struct PokeEntity {
+    pub poke_type: u32,
+    pub strength: u32,
+}
+
+impl Debug for PokeEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "[Poke]: Type: {} Strength: {}",
+            self.poke_type, self.strength
+        )
+    }
+}
+
+impl Display for PokeEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[戳一戳]")
+    }
+}
+
+impl MessageEntity for PokeEntity {
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        let extra = dda!(PokeExtra {
+            r#type: self.poke_type,
+        });
+        vec![dda!(Elem {
+            common_elem: Some(CommonElem {
+                service_type: 2,
+                pb_elem: extra.encode_to_vec(),
+                business_type: self.strength,
+            }),
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let common = elem.common_elem.as_ref()?;
+        if common.service_type != 2 {
+            return None;
+        }
+        let extra = PokeExtra::decode(&*common.pb_elem).ok()?;
+        Some(Self {
+            poke_type: extra.r#type,
+            strength: common.business_type,
+        })
+    }
+}
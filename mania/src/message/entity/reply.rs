@@ -0,0 +1,128 @@
+use super::prelude::*;
+use crate::message::chain::{MessageChain, MessageType};
+use crate::message::entity::Entity;
+
+/// A quoted message, formerly `ForwardEntity` / `Entity::Forward` with `target_uin` now named
+/// `sender_uin`
+#[pack_content(false)]
+#[derive(Default)]
+pub struct ReplyEntity {
+    pub time: DateTime<Utc>,
+    pub message_id: MessageId,
+    /// Sequence of the quoted (source) message
+    pub sequence: u32,
+    pub client_sequence: Option<ClientSequence>,
+    pub uid: Option<String>,
+    /// Uin of the sender of the quoted message
+    pub sender_uin: u32,
+    pub(crate) elems: Vec<Elem>,
+    pub(crate) self_uid: Option<String>,
+}
+
+impl ReplyEntity {
+    pub fn from_chain(chain: &MessageChain) -> Self {
+        let (uid, sender_uin, client_sequence) = match &chain.typ {
+            MessageType::Friend(friend) => (
+                Some(chain.uid.clone()).filter(|uid| !uid.is_empty()),
+                chain.friend_uin,
+                Some(friend.client_sequence),
+            ),
+            MessageType::Group(group) => match &group.group_member_info {
+                Some(member) => (
+                    Some(member.uid.clone()).filter(|uid| !uid.is_empty()),
+                    member.uin,
+                    None,
+                ),
+                None => (None, chain.friend_uin, None),
+            },
+            _ => (None, chain.friend_uin, None),
+        };
+        Self {
+            time: chain.time,
+            message_id: chain.message_id,
+            sequence: chain.sequence,
+            client_sequence,
+            uid,
+            sender_uin,
+            elems: chain.elements.clone(),
+            self_uid: Some(chain.self_uid.clone()).filter(|uid| !uid.is_empty()),
+        }
+    }
+
+    /// Entities of the quoted message, as carried inside the reply
+    pub fn entities(&self) -> Vec<Entity> {
+        Entity::from_elems(&self.elems)
+    }
+
+    /// Text preview of the quoted message
+    pub fn preview(&self) -> String {
+        self.entities().iter().map(|e| e.to_string()).collect()
+    }
+}
+
+impl Debug for ReplyEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "[Reply] Sender: {} Time: {} Sequence: {} Preview: {}",
+            self.sender_uin,
+            self.time,
+            self.sequence,
+            self.preview()
+        )
+    }
+}
+
+impl Display for ReplyEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[回复消息]")
+    }
+}
+
+impl MessageEntity for ReplyEntity {
+    fn pack_element(&self, ctx: &Context) -> Vec<Elem> {
+        let pb_reserve = Preserve {
+            message_id: self.message_id.0,
+            sender_uid: self.uid.clone(),
+            receiver_uid: self
+                .self_uid
+                .clone()
+                .or_else(|| ctx.key_store.uid.load().as_deref().cloned()),
+            client_sequence: self.client_sequence.map(|seq| seq.0),
+        }
+        .encode_to_vec();
+        vec![dda!(Elem {
+            src_msg: Some(dda!(SrcMsg {
+                orig_seqs: vec![self.client_sequence.map_or(self.sequence, |seq| seq.0)],
+                // chains sent by the bot itself carry no sender
+                sender_uin: match self.sender_uin {
+                    0 => **ctx.key_store.uin.load(),
+                    uin => uin,
+                } as u64,
+                time: Some(self.time.timestamp() as i32),
+                elems: self.elems.clone(),
+                pb_reserve: Some(pb_reserve),
+                to_uin: Some(0)
+            }))
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let src = elem.src_msg.as_ref()?;
+        let reserve = src
+            .pb_reserve
+            .as_deref()
+            .and_then(|reserve| Preserve::decode(reserve).ok())
+            .unwrap_or_default();
+        Some(Self {
+            time: DateTime::from_timestamp(src.time.unwrap_or(0) as i64, 0).unwrap_or_default(),
+            message_id: MessageId(reserve.message_id),
+            sequence: src.orig_seqs.first().copied().unwrap_or_default(),
+            client_sequence: reserve.client_sequence.map(ClientSequence),
+            uid: reserve.sender_uid,
+            sender_uin: src.sender_uin as u32,
+            elems: src.elems.clone(),
+            self_uid: reserve.receiver_uid,
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use super::prelude::*;
+
+#[pack_content(false)]
+#[derive(Default)]
+pub struct SpecialPokeEntity {
+    pub id: u32,
+    pub name: String,
+    pub strength: u32,
+}
+
+impl SpecialPokeEntity {
+    pub(crate) const POKE_TYPE: u32 = 126;
+}
+
+impl Debug for SpecialPokeEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "[SpecialPoke]: {} Id: {} Strength: {}",
+            self.name, self.id, self.strength
+        )
+    }
+}
+
+impl Display for SpecialPokeEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[{}]", self.name)
+    }
+}
+
+impl MessageEntity for SpecialPokeEntity {
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        let extra = dda!(PokeExtra {
+            r#type: Self::POKE_TYPE,
+            vas_poke_id: Some(self.id),
+            vas_poke_name: Some(self.name.clone()),
+            vas_poke_min_ver: Some("7.2.0".to_string()),
+            strength: Some(self.strength),
+        });
+        vec![dda!(Elem {
+            common_elem: Some(CommonElem {
+                service_type: 2,
+                pb_elem: extra.encode_to_vec(),
+                business_type: self.strength,
+            }),
+        })]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        let common = elem.common_elem.as_ref()?;
+        if common.service_type != 2 {
+            return None;
+        }
+        let extra = PokeExtra::decode(&*common.pb_elem).ok()?;
+        match (extra.r#type, extra.vas_poke_id) {
+            (Self::POKE_TYPE, Some(id)) => Some(Self {
+                id,
+                name: extra.vas_poke_name.unwrap_or_default(),
+                strength: common.business_type,
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use super::prelude::*;
+use prost::encoding::{DecodeContext, decode_key, skip_field};
+
+/// An element mania does not understand, kept as is so that it can be packed back verbatim
+#[pack_content(false)]
+#[derive(Default)]
+pub struct UnknownEntity {
+    /// The known fields of the element
+    pub elem: Elem,
+    /// The element as received, including the fields `elem` can not hold. Outgoing chains
+    /// carry these bytes instead of `elem`
+    pub raw: Bytes,
+}
+
+impl UnknownEntity {
+    /// Wrap an element without its original bytes, `raw` is encoded from `elem`
+    pub fn new(elem: Elem) -> Self {
+        let raw = Bytes::from(elem.encode_to_vec());
+        Self { elem, raw }
+    }
+
+    pub fn from_raw(raw: Bytes) -> Result<Self, prost::DecodeError> {
+        let elem = Elem::decode(raw.clone())?;
+        Ok(Self { elem, raw })
+    }
+
+    pub(crate) fn with_raw(elem: Elem, raw: Bytes) -> Self {
+        Self { elem, raw }
+    }
+
+    /// Numbers of the top level fields in `raw` that `Elem` does not define
+    pub fn unknown_fields(&self) -> Vec<u32> {
+        let known = field_numbers(&self.elem.encode_to_vec());
+        let mut unknown: Vec<u32> = field_numbers(&self.raw)
+            .into_iter()
+            .filter(|tag| !known.contains(tag))
+            .collect();
+        unknown.dedup();
+        unknown
+    }
+
+    /// Names of the `Elem` fields that are present, with the service type for `CommonElem`
+    pub fn describe(&self) -> String {
+        let elem = &self.elem;
+        let mut fields = Vec::new();
+        macro_rules! collect {
+            ( $( $field:ident => $name:literal ),* $(,)? ) => {
+                $(
+                    if elem.$field.is_some() {
+                        fields.push($name.to_string());
+                    }
+                )*
+            };
+        }
+        collect!(
+            text => "Text",
+            face => "Face",
+            online_image => "OnlineImage",
+            not_online_image => "NotOnlineImage",
+            trans_elem => "TransElem",
+            market_face => "MarketFace",
+            custom_face => "CustomFace",
+            rich_msg => "RichMsg",
+            group_file => "GroupFile",
+            video_file => "VideoFile",
+            anonymous_group_message => "AnonymousGroupMessage",
+            qq_wallet_msg => "QQWalletMsg",
+            custom_elem => "CustomElem",
+            src_msg => "SrcMsg",
+            light_app_elem => "LightAppElem",
+        );
+        if let Some(common) = &elem.common_elem {
+            fields.push(format!(
+                "CommonElem(service: {}, business: {})",
+                common.service_type, common.business_type
+            ));
+        }
+        fields.extend(
+            self.unknown_fields()
+                .into_iter()
+                .map(|tag| format!("Field{}", tag)),
+        );
+        if fields.is_empty() {
+            "no fields".to_string()
+        } else {
+            fields.join(", ")
+        }
+    }
+
+    /// Elements carrying chain metadata rather than content
+    fn is_metadata(elem: &Elem) -> bool {
+        elem.extra_info.is_some() || elem.general_flags.is_some() || elem.elem_flags2.is_some()
+    }
+}
+
+impl Debug for UnknownEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "[Unknown]: {} ({} bytes)",
+            self.describe(),
+            self.raw.len()
+        )
+    }
+}
+
+impl Display for UnknownEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "[未知消息]")
+    }
+}
+
+impl MessageEntity for UnknownEntity {
+    /// Only the known fields, `MessagePacker` writes `raw` to the packet instead
+    fn pack_element(&self, _: &Context) -> Vec<Elem> {
+        vec![self.elem.clone()]
+    }
+
+    fn unpack_element(elem: &Elem) -> Option<Self> {
+        (!Self::is_metadata(elem)).then(|| Self::new(elem.clone()))
+    }
+}
+
+/// Top level field numbers of an encoded message, stops at the first malformed field
+fn field_numbers(mut buf: &[u8]) -> Vec<u32> {
+    let mut tags = Vec::new();
+    while !buf.is_empty() {
+        let Ok((tag, wire_type)) = decode_key(&mut buf) else {
+            break;
+        };
+        if skip_field(wire_type, tag, &mut buf, DecodeContext::default()).is_err() {
+            break;
+        }
+        tags.push(tag);
+    }
+    tags
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unknown_fields_kept() {
+        let text = dda!(Elem {
+            text: Some(dda!(Text {
+                str: Some("hi".to_string()),
+            })),
+        });
+        let mut raw = text.encode_to_vec();
+        // field 1000, length delimited, as a future element type would be
+        raw.extend_from_slice(&[0xc2, 0x3e, 0x02, 0x08, 0x01]);
+        let entity = UnknownEntity::from_raw(Bytes::from(raw.clone())).unwrap();
+        assert_eq!(entity.raw.as_ref(), raw.as_slice());
+        assert_eq!(entity.elem, text);
+        assert_eq!(entity.unknown_fields(), vec![1000]);
+        assert_eq!(entity.describe(), "Text, Field1000");
+    }
+}
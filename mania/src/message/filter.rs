@@ -0,0 +1,12 @@
+/// What to do with messages sent by the bot itself, which are pushed back by the server
+/// both for messages sent by mania and for messages sent from other devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelfMessageFilter {
+    /// Deliver them as normal message events, with `MessageChain::is_self` set
+    #[default]
+    Tag,
+    /// Drop them silently
+    Drop,
+    /// Deliver them only as `SystemEvent::SelfMessageEvent`
+    Separate,
+}
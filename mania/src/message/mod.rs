@@ -1,4 +1,7 @@
 pub mod builder;
 pub mod chain;
+pub mod codec;
 pub mod entity;
+pub mod filter;
 pub mod packer;
+pub mod schema;
@@ -1,15 +1,17 @@
 use crate::core::protos::message::{
-    C2c, ContentHead, FileExtra, Grp, Message, MessageBody, MessageControl, PushMsgBody, RichText,
-    RoutingHead, Trans0X211,
+    C2c, ContentHead, Elem, FileExtra, ForwardHead, Grp, GrpTmp, Message, MessageBody,
+    MessageControl, PushMsgBody, RawMessageBody, RawPushMsgBody, RawRichText, ResponseForward,
+    ResponseGrp, ResponseHead, RichText, RoutingHead, Trans0X211,
 };
 use crate::entity::bot_friend::BotFriend;
 use crate::entity::bot_group_member::{BotGroupMember, FetchGroupMemberStrategy};
 use crate::message::chain::{
     ClientSequence, FriendMessageUniqueElem, GroupMessageUniqueElem, MessageChain, MessageId,
-    MessageType,
+    MessageType, TempMessageUniqueElem,
 };
 use crate::message::entity::Entity;
 use crate::message::entity::file::{FileC2CUnique, FileEntity, FileUnique};
+use crate::utility::random_gen::RandomGenerator;
 use crate::{Context, dda};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
@@ -18,14 +20,59 @@ use prost::Message as _;
 pub(crate) struct MessagePacker;
 
 impl MessagePacker {
-    #[allow(clippy::let_and_return)] // FIXME: remove this
-    pub(crate) fn build(chain: &MessageChain, ctx: &Context) -> Message {
-        let base = MessagePacker::build_packet_base(chain, ctx);
+    /// `ContentHead.type` of messages received through a group temp session
+    const TEMP_MESSAGE_TYPE: u32 = 141;
+
+    /// Whether the packed chain is larger than `ClientConfig::max_message_size`, only accurate
+    /// after the resources of the chain are uploaded
+    pub(crate) fn exceeds_size_limit(chain: &MessageChain, ctx: &Context) -> bool {
+        MessagePacker::build(chain, ctx).len() > ctx.config.max_message_size
+    }
+
+    /// Pack the chain into an encoded `Message`
+    pub(crate) fn build(chain: &MessageChain, ctx: &Context) -> Vec<u8> {
+        let mut base = MessagePacker::build_packet_base(chain, ctx);
         // TODO: BuildAdditional(chain, message);
-        base
+        // the body is encoded through its raw view, so that unknown elements are written as
+        // received, fields of a message may come in any order
+        let body = base
+            .body
+            .take()
+            .map(|body| Self::raw_body(body, &chain.entities, ctx));
+        let mut buf = base.encode_to_vec();
+        if let Some(body) = body {
+            prost::encoding::message::encode(3, &body, &mut buf);
+        }
+        buf
+    }
+
+    /// The raw view of a packed body, its elements packed from `entities`
+    fn raw_body(body: MessageBody, entities: &[Entity], ctx: &Context) -> RawMessageBody {
+        let rich_text = body.rich_text.unwrap_or_default();
+        RawMessageBody {
+            rich_text: Some(RawRichText {
+                attr: rich_text.attr,
+                elems: entities
+                    .iter()
+                    .flat_map(|entity| entity.to_raw_elems(ctx))
+                    .collect(),
+                not_online_file: rich_text.not_online_file,
+                ptt: rich_text.ptt,
+            }),
+            msg_content: body.msg_content,
+            msg_encrypt_content: body.msg_encrypt_content,
+        }
+    }
+
+    /// Elements of a message as received, see `Entity::from_raw_elems`
+    pub(crate) fn raw_elems(raw: Option<RawPushMsgBody>) -> Vec<Vec<u8>> {
+        raw.and_then(|raw| raw.body)
+            .and_then(|body| body.rich_text)
+            .map(|rich_text| rich_text.elems)
+            .unwrap_or_default()
     }
 
-    fn build_packet_base(chain: &MessageChain, ctx: &Context) -> Message {
+    fn build_packet_base(chain: &MessageChain, _: &Context) -> Message {
         dda!(Message {
             routing_head: Some(dda!(RoutingHead {
                 c2c: match (&chain.typ, Entity::need_pack_content(&chain.entities)) {
@@ -41,6 +88,13 @@ impl MessagePacker {
                     }),
                     _ => None,
                 },
+                grp_tmp: match &chain.typ {
+                    MessageType::Temp(temp) => Some(GrpTmp {
+                        group_uin: Some(temp.group_uin),
+                        to_uin: Some(chain.friend_uin),
+                    }),
+                    _ => None,
+                },
                 trans0_x211: match Entity::need_pack_content(&chain.entities) {
                     true => Some(dda!(Trans0X211 {
                         cc_cmd: Some(4),
@@ -54,14 +108,9 @@ impl MessagePacker {
                 sub_type: Some(0),
                 c2c_cmd: Some(0),
             })),
+            // elements are packed in `build`
             body: Some(dda!(MessageBody {
-                rich_text: Some(dda!(RichText {
-                    elems: chain
-                        .entities
-                        .iter()
-                        .flat_map(|entity| entity.pack_element(ctx))
-                        .collect(),
-                })),
+                rich_text: Some(RichText::default()),
                 msg_content: chain
                     .entities
                     .iter()
@@ -83,8 +132,88 @@ impl MessagePacker {
         })
     }
 
+    /// Build a node of a multi message (merged forward) bundle, the sender name
+    /// shown in the bundle is taken from the friend nickname or the group card
+    pub(crate) fn build_fake(chain: &MessageChain, ctx: &Context) -> RawPushMsgBody {
+        let group_uin = match &chain.typ {
+            MessageType::Group(group) => Some(group.group_uin),
+            _ => None,
+        };
+        let (friend_name, member_card) = match &chain.typ {
+            MessageType::Friend(friend) => (
+                friend.friend_info.as_ref().map(|f| f.nickname.clone()),
+                None,
+            ),
+            MessageType::Group(group) => (
+                None,
+                group.group_member_info.as_ref().and_then(|member| {
+                    member
+                        .member_card
+                        .clone()
+                        .or_else(|| member.member_name.clone())
+                }),
+            ),
+            _ => (None, None),
+        };
+        let time = match chain.time.timestamp() {
+            0 => Utc::now().timestamp(),
+            ts => ts,
+        };
+        RawPushMsgBody {
+            response_head: Some(dda!(ResponseHead {
+                from_uin: chain.friend_uin,
+                from_uid: Some(chain.uid.clone()),
+                to_uid: ctx.key_store.uid.load().as_deref().cloned(),
+                forward: Some(ResponseForward {
+                    friend_name: Some(friend_name.unwrap_or_default()),
+                }),
+                grp: group_uin.map(|group_uin| {
+                    dda!(ResponseGrp {
+                        group_code: Some(group_uin as u64),
+                        group_card: Some(member_card.unwrap_or_default()),
+                        group_card_type: Some(2),
+                    })
+                }),
+            })),
+            content_head: Some(dda!(ContentHead {
+                r#type: if group_uin.is_some() { 82 } else { 529 },
+                sub_type: if group_uin.is_some() { None } else { Some(4) },
+                div_seq: if group_uin.is_some() { None } else { Some(4) },
+                random: Some(RandomGenerator::random_num(100000000, i32::MAX as u32)),
+                sequence: Some(match chain.sequence {
+                    0 => RandomGenerator::random_num(1000000, 9999999),
+                    seq => seq,
+                }),
+                time_stamp: Some(time as u32),
+                pkg_num: Some(1),
+                pkg_index: Some(0),
+                forward: Some(dda!(ForwardHead {
+                    field1: Some(0),
+                    field2: Some(0),
+                    field3: if group_uin.is_some() { None } else { Some(2) },
+                    avatar: Some(format!(
+                        "https://q.qlogo.cn/headimg_dl?dst_uin={}&spec=640&img_type=jpg",
+                        chain.friend_uin
+                    )),
+                })),
+            })),
+            body: Some(dda!(RawMessageBody {
+                rich_text: Some(dda!(RawRichText {
+                    elems: chain
+                        .entities
+                        .iter()
+                        .flat_map(|entity| entity.to_raw_elems(ctx))
+                        .collect(),
+                })),
+            })),
+        }
+    }
+
+    /// Parse a received message, `raw_elems` are its elements as received (see
+    /// `MessagePacker::raw_elems`), empty if not at hand
     pub(crate) fn parse_chain(
         push_msg_body: PushMsgBody,
+        raw_elems: Vec<Vec<u8>>,
         ctx: &Context,
     ) -> Result<MessageChain, String> {
         let response_head = push_msg_body
@@ -101,14 +230,18 @@ impl MessagePacker {
             .and_then(|body| body.rich_text.as_ref())
             .map_or(0, |rich_text| rich_text.elems.len());
         let mut entities: Vec<Entity> = Vec::with_capacity(pre_len);
+        let mut elements: Vec<Elem> = Vec::with_capacity(pre_len);
         if let Some(rich_text) = push_msg_body
             .body
             .as_ref()
             .and_then(|body| body.rich_text.as_ref())
         {
-            entities.extend(rich_text.elems.iter().filter_map(Entity::unpack_element));
+            entities.extend(Entity::from_raw_elems(&rich_text.elems, &raw_elems));
+            elements.extend(rich_text.elems.iter().cloned());
         }
-        if let Some(grp) = &response_head.grp {
+        if let Some(grp) = &response_head.grp
+            && content_head.r#type != Self::TEMP_MESSAGE_TYPE
+        {
             let (mut ex_gf, mut ex_info) = match ctx.config.fetch_group_member_strategy {
                 FetchGroupMemberStrategy::Simple => {
                     let mut extra_entities = Vec::with_capacity(2);
@@ -172,12 +305,21 @@ impl MessagePacker {
                 )
                 .ok_or("failed to parse timestamp")?,
                 sequence: content_head.sequence.unwrap_or_default(),
-                entities: entities,
+                elements,
+                entities,
             }));
         }
-        Ok(dda!(MessageChain {
+        Ok(MessageChain {
             typ: match content_head.r#type {
-                141 => MessageType::Temp,
+                // the group a temp session was started from is only known when the push carries
+                // it, otherwise it stays 0 and replying needs the group set explicitly
+                Self::TEMP_MESSAGE_TYPE => MessageType::Temp(TempMessageUniqueElem {
+                    group_uin: response_head
+                        .grp
+                        .as_ref()
+                        .and_then(|grp| grp.group_code)
+                        .unwrap_or_default() as u32,
+                }),
                 _ => MessageType::Friend(FriendMessageUniqueElem {
                     friend_info: None,
                     client_sequence: ClientSequence(content_head.sequence.unwrap_or_default()),
@@ -190,16 +332,19 @@ impl MessagePacker {
             message_id: MessageId(content_head.msg_uid.unwrap_or_default()),
             time: DateTime::<Utc>::from_timestamp(
                 content_head.time_stamp.unwrap_or_default() as i64,
-                0
+                0,
             )
             .ok_or("failed to parse timestamp")?,
             sequence: content_head.nt_msg_seq.unwrap_or_default(),
-            entities: entities,
-        }))
+            elements,
+            entities,
+            is_self: false,
+        })
     }
 
     pub(crate) fn parse_fake_chain(
         body: PushMsgBody,
+        raw_elems: Vec<Vec<u8>>,
         ctx: &Context,
     ) -> Result<MessageChain, String> {
         let make_group_extra = |body: &PushMsgBody| -> Option<GroupMessageUniqueElem> {
@@ -268,7 +413,7 @@ impl MessagePacker {
                     .ok_or_else(|| "failed to make_friend_extra".to_string())?,
             )
         };
-        let mut chain = MessagePacker::parse_chain(body, ctx)?;
+        let mut chain = MessagePacker::parse_chain(body, raw_elems, ctx)?;
         chain.typ = typ;
         Ok(chain)
     }
@@ -283,7 +428,7 @@ impl MessagePacker {
             .and_then(|b| b.msg_content.clone())
             .ok_or_else(|| "missing msg_content".to_string())?;
 
-        let mut base_chain = MessagePacker::parse_chain(body, ctx)?;
+        let mut base_chain = MessagePacker::parse_chain(body, Vec::new(), ctx)?;
         let extra = FileExtra::decode(Bytes::from(msg_content))
             .map_err(|e| format!("failed to decode FileExtra: {:?}", e))?;
         let file = extra
@@ -311,3 +456,84 @@ impl MessagePacker {
         Ok(base_chain)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ClientConfig;
+    use crate::message::entity::text::TextEntity;
+    use crate::message::entity::unknown::UnknownEntity;
+
+    #[test]
+    fn test_size_limit_threshold() {
+        let mut chain = MessageChain::friend(10000, "u_test", "u_self");
+        chain.entities.push(Entity::Text(TextEntity {
+            text: "a".repeat(4000),
+        }));
+        let ctx = Context::for_test(ClientConfig::default());
+        let size = MessagePacker::build(&chain, &ctx).len();
+        // random numbers in the heads make the size vary by a byte or two between builds
+        let above = Context::for_test(ClientConfig {
+            max_message_size: size + 8,
+            ..Default::default()
+        });
+        assert!(!MessagePacker::exceeds_size_limit(&chain, &above));
+        let below = Context::for_test(ClientConfig {
+            max_message_size: size - 8,
+            ..Default::default()
+        });
+        assert!(MessagePacker::exceeds_size_limit(&chain, &below));
+        // the default limit is kept for text well below it
+        chain.entities[0] = Entity::Text(TextEntity {
+            text: "a".repeat(100),
+        });
+        assert!(!MessagePacker::exceeds_size_limit(&chain, &ctx));
+    }
+
+    #[test]
+    fn test_unknown_elem_packed_verbatim() {
+        // a text element followed by field 1000, unknown to `Elem`
+        let raw = [
+            0x0a, 0x04, 0x0a, 0x02, b'h', b'i', 0xc2, 0x3e, 0x02, 0x08, 0x01,
+        ];
+        let mut chain = MessageChain::friend(10000, "u_test", "u_self");
+        chain.entities.push(Entity::Unknown(
+            UnknownEntity::from_raw(Bytes::copy_from_slice(&raw)).unwrap(),
+        ));
+        let ctx = Context::for_test(ClientConfig::default());
+        let packet = MessagePacker::build(&chain, &ctx);
+        assert!(packet.windows(raw.len()).any(|window| window == raw));
+        let message = Message::decode(packet.as_slice()).unwrap();
+        let elems = message.body.unwrap().rich_text.unwrap().elems;
+        assert_eq!(elems.len(), 1);
+        assert!(elems[0].text.is_some());
+    }
+
+    #[test]
+    fn test_temp_chain_keeps_group() {
+        let body = |grp: Option<ResponseGrp>| {
+            dda!(PushMsgBody {
+                response_head: Some(dda!(ResponseHead {
+                    from_uin: 10001,
+                    to_uin: 10000,
+                    grp,
+                })),
+                content_head: Some(dda!(ContentHead {
+                    r#type: MessagePacker::TEMP_MESSAGE_TYPE,
+                })),
+            })
+        };
+        let ctx = Context::for_test(ClientConfig::default());
+        let chain = MessagePacker::parse_chain(
+            body(Some(dda!(ResponseGrp {
+                group_code: Some(123456),
+            }))),
+            Vec::new(),
+            &ctx,
+        )
+        .unwrap();
+        assert!(matches!(chain.typ, MessageType::Temp(temp) if temp.group_uin == 123456));
+        let chain = MessagePacker::parse_chain(body(None), Vec::new(), &ctx).unwrap();
+        assert!(matches!(chain.typ, MessageType::Temp(temp) if temp.group_uin == 0));
+    }
+}
@@ -0,0 +1,936 @@
+//! Stable JSON representation of [`MessageChain`] and [`Entity`]
+//!
+//! Every serialized chain carries a `version` field, bump [`SCHEMA_VERSION`] whenever a field is
+//! renamed or removed. Protobuf payloads needed to send an entity again (e.g. the `MsgInfo` of
+//! uploaded media) are kept as hex strings, so deserialized chains stay sendable where possible.
+//! The received elements of a chain are kept the same way, so that a deserialized chain can still
+//! be quoted with [`ReplyEntity::from_chain`].
+
+use crate::core::protos::message::Elem;
+use crate::core::protos::service::oidb::{IndexNode, MsgInfo};
+use crate::dda;
+use crate::entity::bot_friend::BotFriend;
+use crate::entity::bot_group_member::{BotGroupMember, GroupMemberPermission};
+use crate::message::chain::{
+    ClientSequence, FriendMessageUniqueElem, GroupMessageUniqueElem, MessageChain, MessageId,
+    MessageType, TempMessageUniqueElem,
+};
+use crate::message::entity::Entity;
+use crate::message::entity::bounce_face::BounceFaceEntity;
+use crate::message::entity::extra_general_flags::ExtraGeneralFlagsEntity;
+use crate::message::entity::extra_info::ExtraInfoEntity;
+use crate::message::entity::face::FaceEntity;
+use crate::message::entity::file::FileEntity;
+use crate::message::entity::image::ImageEntity;
+use crate::message::entity::json::JsonEntity;
+use crate::message::entity::keyboard::{KeyboardButton, KeyboardEntity};
+use crate::message::entity::light_app::LightAppEntity;
+use crate::message::entity::long_msg::LongMsgEntity;
+use crate::message::entity::markdown::MarkdownEntity;
+use crate::message::entity::market_face::MarketFaceEntity;
+use crate::message::entity::mention::MentionEntity;
+use crate::message::entity::multi_msg::MultiMsgEntity;
+use crate::message::entity::poke::PokeEntity;
+use crate::message::entity::record::RecordEntity;
+use crate::message::entity::reply::ReplyEntity;
+use crate::message::entity::special_poke::SpecialPokeEntity;
+use crate::message::entity::text::TextEntity;
+use crate::message::entity::unknown::UnknownEntity;
+use crate::message::entity::video::VideoEntity;
+use crate::message::entity::xml::XmlEntity;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use prost::Message;
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ChainKind {
+    Friend {
+        client_sequence: u32,
+        #[serde(default)]
+        friend: Option<BotFriend>,
+    },
+    Group {
+        group_uin: u32,
+        #[serde(default)]
+        member: Option<BotGroupMember>,
+    },
+    Temp {
+        #[serde(default)]
+        group_uin: u32,
+    },
+    None,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Sender {
+    uin: u32,
+    #[serde(default)]
+    uid: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ChainRepr {
+    version: u32,
+    #[serde(flatten)]
+    kind: ChainKind,
+    sender: Sender,
+    target_uin: u32,
+    #[serde(default)]
+    self_uid: String,
+    message_id: u64,
+    sequence: u32,
+    time: DateTime<Utc>,
+    #[serde(default)]
+    is_self: bool,
+    entities: Vec<Entity>,
+    #[serde(default)]
+    elements: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum EntityRepr<'a> {
+    Text {
+        text: String,
+    },
+    Mention {
+        uin: u32,
+        uid: String,
+        name: Option<String>,
+    },
+    /// `sys_face_entry` is not stored, it is looked up again by `face_id` from the (cached) sys
+    /// face list when the chain is sent
+    Face {
+        face_id: u16,
+        is_large_face: bool,
+    },
+    BounceFace {
+        face_id: u32,
+        count: u32,
+        name: String,
+    },
+    Poke {
+        poke_type: u32,
+        strength: u32,
+    },
+    SpecialPoke {
+        id: u32,
+        name: String,
+        strength: u32,
+    },
+    MarketFace {
+        emoji_id: String,
+        emoji_package_id: u32,
+        key: String,
+        summary: String,
+    },
+    Json {
+        json: String,
+        res_id: String,
+    },
+    Xml {
+        xml: String,
+        service_id: i32,
+    },
+    LightApp {
+        app_name: String,
+        payload: String,
+    },
+    Markdown {
+        content: String,
+    },
+    Keyboard {
+        rows: Vec<Vec<KeyboardButton>>,
+    },
+    Reply {
+        message_id: u64,
+        sequence: u32,
+        client_sequence: Option<u32>,
+        sender_uin: u32,
+        sender_uid: Option<String>,
+        time: DateTime<Utc>,
+        /// Only for reading, the quoted message is rebuilt from `elems`
+        #[serde(default, skip_deserializing)]
+        preview: String,
+        elems: Vec<String>,
+    },
+    Image {
+        url: String,
+        file_path: Option<String>,
+        md5: String,
+        size: u32,
+        width: u32,
+        height: u32,
+        sub_type: u32,
+        summary: Option<String>,
+        is_group: bool,
+        msg_info: Option<String>,
+    },
+    Record {
+        url: String,
+        name: String,
+        md5: String,
+        length: u32,
+        size: u32,
+        uuid: Option<String>,
+        sha1: Option<String>,
+        msg_info: Option<String>,
+    },
+    Video {
+        url: String,
+        file_name: String,
+        hash: String,
+        width: i32,
+        height: i32,
+        size: i32,
+        length: i32,
+        uuid: Option<String>,
+        node: Option<String>,
+        msg_info: Option<String>,
+    },
+    File {
+        file_name: String,
+        file_size: u64,
+        file_md5: String,
+        file_sha1: String,
+        file_url: Option<String>,
+    },
+    MultiMsg {
+        res_id: String,
+        detail_str: Option<String>,
+        chains: Nested<'a, Vec<MessageChain>>,
+    },
+    LongMsg {
+        res_id: String,
+        chain: Nested<'a, MessageChain>,
+    },
+    ExtraInfo {
+        group_member_special_title: Option<String>,
+    },
+    ExtraGeneralFlags {
+        permission: GroupMemberPermission,
+        new_group_level: u32,
+    },
+    Unknown {
+        raw: String,
+    },
+}
+
+/// Nested chains are borrowed when serializing, since entities can not be cloned
+enum Nested<'a, T> {
+    Borrowed(&'a T),
+    Owned(T),
+}
+
+impl<T> Nested<'_, T> {
+    fn into_owned(self) -> Result<T, String> {
+        match self {
+            Nested::Owned(inner) => Ok(inner),
+            Nested::Borrowed(_) => Err("borrowed nested chain can not be rebuilt".to_string()),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Nested<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Nested::Borrowed(inner) => inner.serialize(serializer),
+            Nested::Owned(inner) => inner.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nested<'_, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Nested::Owned)
+    }
+}
+
+fn encode_proto<T: Message>(message: &T) -> String {
+    hex::encode(message.encode_to_vec())
+}
+
+fn decode_proto<T: Message + Default>(hex_str: &str) -> Result<T, String> {
+    let bytes = hex::decode(hex_str).map_err(|e| e.to_string())?;
+    T::decode(bytes.as_slice()).map_err(|e| e.to_string())
+}
+
+fn decode_hex(hex_str: &str) -> Result<Bytes, String> {
+    hex::decode(hex_str)
+        .map(Bytes::from)
+        .map_err(|e| e.to_string())
+}
+
+impl<'a> From<&'a Entity> for EntityRepr<'a> {
+    fn from(entity: &'a Entity) -> Self {
+        match entity {
+            Entity::Text(e) => Self::Text {
+                text: e.text.clone(),
+            },
+            Entity::Mention(e) => Self::Mention {
+                uin: e.uin,
+                uid: e.uid.clone(),
+                name: e.name.clone(),
+            },
+            Entity::Face(e) => Self::Face {
+                face_id: e.face_id,
+                is_large_face: e.is_large_face,
+            },
+            Entity::BounceFace(e) => Self::BounceFace {
+                face_id: e.face_id,
+                count: e.count,
+                name: e.name.clone(),
+            },
+            Entity::Poke(e) => Self::Poke {
+                poke_type: e.poke_type,
+                strength: e.strength,
+            },
+            Entity::SpecialPoke(e) => Self::SpecialPoke {
+                id: e.id,
+                name: e.name.clone(),
+                strength: e.strength,
+            },
+            Entity::MarketFace(e) => Self::MarketFace {
+                emoji_id: e.emoji_id.clone(),
+                emoji_package_id: e.emoji_package_id,
+                key: e.key.clone(),
+                summary: e.summary.clone(),
+            },
+            Entity::Json(e) => Self::Json {
+                json: e.json.clone(),
+                res_id: e.res_id.clone(),
+            },
+            Entity::Xml(e) => Self::Xml {
+                xml: e.xml.clone(),
+                service_id: e.service_id,
+            },
+            Entity::LightApp(e) => Self::LightApp {
+                app_name: e.app_name.clone(),
+                payload: e.payload.clone(),
+            },
+            Entity::Markdown(e) => Self::Markdown {
+                content: e.content.clone(),
+            },
+            Entity::Keyboard(e) => Self::Keyboard {
+                rows: e.rows.clone(),
+            },
+            Entity::Reply(e) => Self::Reply {
+                message_id: e.message_id.0,
+                sequence: e.sequence,
+                client_sequence: e.client_sequence.map(|seq| seq.0),
+                sender_uin: e.sender_uin,
+                sender_uid: e.uid.clone(),
+                time: e.time,
+                preview: e.preview(),
+                elems: e.elems.iter().map(encode_proto).collect(),
+            },
+            Entity::Image(e) => Self::Image {
+                url: e.url.clone(),
+                file_path: e.file_path.clone(),
+                md5: hex::encode(&e.md5),
+                size: e.size,
+                width: e.width,
+                height: e.height,
+                sub_type: e.sub_type,
+                summary: e.summary.clone(),
+                is_group: e.is_group,
+                msg_info: e.msg_info.as_ref().map(encode_proto),
+            },
+            Entity::Record(e) => Self::Record {
+                url: e.audio_url.clone(),
+                name: e.audio_name.clone(),
+                md5: hex::encode(&e.audio_md5),
+                length: e.audio_length,
+                size: e.file_size,
+                uuid: e.audio_uuid.clone(),
+                sha1: e.file_sha1.clone(),
+                msg_info: e.msg_info.as_ref().map(encode_proto),
+            },
+            Entity::Video(e) => Self::Video {
+                url: e.video_url.clone(),
+                file_name: e.file_name.clone(),
+                hash: e.video_hash.clone(),
+                width: e.width,
+                height: e.height,
+                size: e.video_size,
+                length: e.video_length,
+                uuid: e.video_uuid.clone(),
+                node: e.node.as_ref().map(encode_proto),
+                msg_info: e.msg_info.as_ref().map(encode_proto),
+            },
+            Entity::File(e) => Self::File {
+                file_name: e.file_name.clone(),
+                file_size: e.file_size,
+                file_md5: hex::encode(&e.file_md5),
+                file_sha1: hex::encode(&e.file_sha1),
+                file_url: e.file_url.clone(),
+            },
+            Entity::MultiMsg(e) => Self::MultiMsg {
+                res_id: e.res_id.clone(),
+                detail_str: e.detail_str.clone(),
+                chains: Nested::Borrowed(&e.chains),
+            },
+            Entity::LongMsg(e) => Self::LongMsg {
+                res_id: e.res_id.clone(),
+                chain: Nested::Borrowed(&e.chain),
+            },
+            Entity::ExtraInfo(e) => Self::ExtraInfo {
+                group_member_special_title: e.group_member_special_title.clone(),
+            },
+            Entity::ExtraGeneralFlags(e) => Self::ExtraGeneralFlags {
+                permission: e.permission.clone(),
+                new_group_level: e.new_group_level,
+            },
+            Entity::Unknown(e) => Self::Unknown {
+                raw: hex::encode(&e.raw),
+            },
+        }
+    }
+}
+
+impl TryFrom<EntityRepr<'_>> for Entity {
+    type Error = String;
+
+    fn try_from(repr: EntityRepr<'_>) -> Result<Self, Self::Error> {
+        Ok(match repr {
+            EntityRepr::Text { text } => Entity::Text(TextEntity { text }),
+            EntityRepr::Mention { uin, uid, name } => {
+                Entity::Mention(MentionEntity { uin, uid, name })
+            }
+            EntityRepr::Face {
+                face_id,
+                is_large_face,
+            } => Entity::Face(dda!(FaceEntity {
+                face_id,
+                is_large_face,
+            })),
+            EntityRepr::BounceFace {
+                face_id,
+                count,
+                name,
+            } => Entity::BounceFace(BounceFaceEntity {
+                face_id,
+                count,
+                name,
+            }),
+            EntityRepr::Poke {
+                poke_type,
+                strength,
+            } => Entity::Poke(PokeEntity {
+                poke_type,
+                strength,
+            }),
+            EntityRepr::SpecialPoke { id, name, strength } => {
+                Entity::SpecialPoke(SpecialPokeEntity { id, name, strength })
+            }
+            EntityRepr::MarketFace {
+                emoji_id,
+                emoji_package_id,
+                key,
+                summary,
+            } => Entity::MarketFace(MarketFaceEntity {
+                emoji_id,
+                emoji_package_id,
+                key,
+                summary,
+            }),
+            EntityRepr::Json { json, res_id } => Entity::Json(JsonEntity { json, res_id }),
+            EntityRepr::Xml { xml, service_id } => Entity::Xml(XmlEntity { xml, service_id }),
+            EntityRepr::LightApp { app_name, payload } => {
+                Entity::LightApp(LightAppEntity { app_name, payload })
+            }
+            EntityRepr::Markdown { content } => Entity::Markdown(MarkdownEntity { content }),
+            EntityRepr::Keyboard { rows } => Entity::Keyboard(KeyboardEntity { rows }),
+            EntityRepr::Reply {
+                message_id,
+                sequence,
+                client_sequence,
+                sender_uin,
+                sender_uid,
+                time,
+                elems,
+                ..
+            } => Entity::Reply(dda!(ReplyEntity {
+                time,
+                message_id: MessageId(message_id),
+                sequence,
+                client_sequence: client_sequence.map(ClientSequence),
+                uid: sender_uid,
+                sender_uin,
+                elems: elems
+                    .iter()
+                    .map(|elem| decode_proto::<Elem>(elem))
+                    .collect::<Result<_, _>>()?,
+            })),
+            EntityRepr::Image {
+                url,
+                file_path,
+                md5,
+                size,
+                width,
+                height,
+                sub_type,
+                summary,
+                is_group,
+                msg_info,
+            } => Entity::Image(dda!(ImageEntity {
+                height,
+                width,
+                file_path,
+                md5: decode_hex(&md5)?,
+                size,
+                url,
+                msg_info: msg_info.as_deref().map(decode_proto).transpose()?,
+                summary,
+                sub_type,
+                is_group,
+            })),
+            EntityRepr::Record {
+                url,
+                name,
+                md5,
+                length,
+                size,
+                uuid,
+                sha1,
+                msg_info,
+            } => Entity::Record(dda!(RecordEntity {
+                audio_length: length,
+                audio_md5: decode_hex(&md5)?,
+                audio_name: name,
+                audio_url: url,
+                file_size: size,
+                audio_uuid: uuid,
+                file_sha1: sha1,
+                msg_info: msg_info.as_deref().map(decode_proto).transpose()?,
+            })),
+            EntityRepr::Video {
+                url,
+                file_name,
+                hash,
+                width,
+                height,
+                size,
+                length,
+                uuid,
+                node,
+                msg_info,
+            } => Entity::Video(dda!(VideoEntity {
+                file_name,
+                video_hash: hash,
+                height,
+                width,
+                video_size: size,
+                video_length: length,
+                video_url: url,
+                node: node.as_deref().map(decode_proto::<IndexNode>).transpose()?,
+                video_uuid: uuid,
+                msg_info: msg_info
+                    .as_deref()
+                    .map(decode_proto::<MsgInfo>)
+                    .transpose()?,
+            })),
+            EntityRepr::File {
+                file_name,
+                file_size,
+                file_md5,
+                file_sha1,
+                file_url,
+            } => Entity::File(dda!(FileEntity {
+                file_size,
+                file_name,
+                file_md5: decode_hex(&file_md5)?,
+                file_url,
+                file_sha1: decode_hex(&file_sha1)?,
+            })),
+            EntityRepr::MultiMsg {
+                res_id,
+                detail_str,
+                chains,
+            } => Entity::MultiMsg(MultiMsgEntity {
+                res_id,
+                chains: chains.into_owned()?,
+                detail_str,
+            }),
+            EntityRepr::LongMsg { res_id, chain } => Entity::LongMsg(LongMsgEntity {
+                res_id,
+                chain: chain.into_owned()?,
+            }),
+            EntityRepr::ExtraInfo {
+                group_member_special_title,
+            } => Entity::ExtraInfo(ExtraInfoEntity {
+                group_member_special_title,
+            }),
+            EntityRepr::ExtraGeneralFlags {
+                permission,
+                new_group_level,
+            } => Entity::ExtraGeneralFlags(ExtraGeneralFlagsEntity {
+                permission,
+                new_group_level,
+            }),
+            EntityRepr::Unknown { raw } => Entity::Unknown(
+                UnknownEntity::from_raw(decode_hex(&raw)?).map_err(|e| e.to_string())?,
+            ),
+        })
+    }
+}
+
+impl From<&MessageType> for ChainKind {
+    fn from(typ: &MessageType) -> Self {
+        match typ {
+            MessageType::Friend(friend) => ChainKind::Friend {
+                client_sequence: friend.client_sequence.0,
+                friend: friend.friend_info.clone(),
+            },
+            MessageType::Group(group) => ChainKind::Group {
+                group_uin: group.group_uin,
+                member: group.group_member_info.clone(),
+            },
+            MessageType::Temp(temp) => ChainKind::Temp {
+                group_uin: temp.group_uin,
+            },
+            MessageType::None => ChainKind::None,
+        }
+    }
+}
+
+impl From<ChainKind> for MessageType {
+    fn from(kind: ChainKind) -> Self {
+        match kind {
+            ChainKind::Friend {
+                client_sequence,
+                friend,
+            } => MessageType::Friend(FriendMessageUniqueElem {
+                friend_info: friend,
+                client_sequence: ClientSequence(client_sequence),
+            }),
+            ChainKind::Group { group_uin, member } => MessageType::Group(GroupMessageUniqueElem {
+                group_uin,
+                group_member_info: member,
+            }),
+            ChainKind::Temp { group_uin } => MessageType::Temp(TempMessageUniqueElem { group_uin }),
+            ChainKind::None => MessageType::None,
+        }
+    }
+}
+
+impl Serialize for Entity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EntityRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Entity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Entity::try_from(EntityRepr::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for MessageChain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ChainReprRef {
+            version: SCHEMA_VERSION,
+            kind: ChainKind::from(&self.typ),
+            sender: Sender {
+                uin: self.friend_uin,
+                uid: self.uid.clone(),
+            },
+            target_uin: self.target_uin,
+            self_uid: &self.self_uid,
+            message_id: self.message_id.0,
+            sequence: self.sequence,
+            time: self.time,
+            is_self: self.is_self,
+            entities: &self.entities,
+            elements: self.elements.iter().map(encode_proto).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageChain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ChainRepr::deserialize(deserializer)?;
+        if repr.version > SCHEMA_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported message chain schema version {} (expected <= {})",
+                repr.version, SCHEMA_VERSION
+            )));
+        }
+        Ok(MessageChain {
+            typ: repr.kind.into(),
+            uid: repr.sender.uid,
+            self_uid: repr.self_uid,
+            target_uin: repr.target_uin,
+            friend_uin: repr.sender.uin,
+            message_id: MessageId(repr.message_id),
+            time: repr.time,
+            sequence: repr.sequence,
+            entities: repr.entities,
+            elements: repr
+                .elements
+                .iter()
+                .map(|elem| decode_proto::<Elem>(elem))
+                .collect::<Result<_, _>>()
+                .map_err(D::Error::custom)?,
+            is_self: repr.is_self,
+        })
+    }
+}
+
+/// Borrowing twin of [`ChainRepr`], avoids cloning the entity list when serializing
+#[derive(Serialize)]
+struct ChainReprRef<'a> {
+    version: u32,
+    #[serde(flatten)]
+    kind: ChainKind,
+    sender: Sender,
+    target_uin: u32,
+    self_uid: &'a str,
+    message_id: u64,
+    sequence: u32,
+    time: DateTime<Utc>,
+    is_self: bool,
+    entities: &'a [Entity],
+    elements: Vec<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::entity::keyboard::{ButtonActionType, ButtonPermissionType};
+    use serde_json::Value;
+
+    fn nested_chain(text: &str) -> MessageChain {
+        let mut chain = MessageChain::fake(10001, "nested");
+        chain.entities.push(Entity::Text(TextEntity {
+            text: text.to_string(),
+        }));
+        chain
+    }
+
+    fn every_entity() -> Vec<Entity> {
+        vec![
+            Entity::Text(TextEntity {
+                text: "text".to_string(),
+            }),
+            Entity::Mention(MentionEntity {
+                uin: 10001,
+                uid: "u_mention".to_string(),
+                name: Some("@mention".to_string()),
+            }),
+            Entity::Face(dda!(FaceEntity {
+                face_id: 14,
+                is_large_face: true,
+            })),
+            Entity::BounceFace(BounceFaceEntity {
+                face_id: 358,
+                count: 3,
+                name: "dice".to_string(),
+            }),
+            Entity::Poke(PokeEntity {
+                poke_type: 1,
+                strength: 2,
+            }),
+            Entity::SpecialPoke(SpecialPokeEntity {
+                id: 5,
+                name: "special".to_string(),
+                strength: 1,
+            }),
+            Entity::MarketFace(MarketFaceEntity {
+                emoji_id: "0123abcd".to_string(),
+                emoji_package_id: 233,
+                key: "key".to_string(),
+                summary: "[market]".to_string(),
+            }),
+            Entity::Json(JsonEntity {
+                json: r#"{"app":"test"}"#.to_string(),
+                res_id: "res_json".to_string(),
+            }),
+            Entity::Xml(XmlEntity {
+                xml: "<msg/>".to_string(),
+                service_id: 35,
+            }),
+            Entity::LightApp(LightAppEntity {
+                app_name: "com.tencent.test".to_string(),
+                payload: r#"{"app":"com.tencent.test"}"#.to_string(),
+            }),
+            Entity::Markdown(MarkdownEntity::new("# title")),
+            Entity::Keyboard(KeyboardEntity {
+                rows: vec![vec![dda!(KeyboardButton {
+                    id: "1".to_string(),
+                    label: "label".to_string(),
+                    action_type: ButtonActionType::Callback,
+                    permission_type: ButtonPermissionType::Everyone,
+                    data: "data".to_string(),
+                })]],
+            }),
+            Entity::Reply(dda!(ReplyEntity {
+                time: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                message_id: MessageId(42),
+                sequence: 7,
+                client_sequence: Some(ClientSequence(8)),
+                uid: Some("u_reply".to_string()),
+                sender_uin: 10002,
+                elems: vec![Elem::decode([0x0a, 0x04, 0x0a, 0x02, b'h', b'i'].as_slice()).unwrap()],
+            })),
+            Entity::Image(dda!(ImageEntity {
+                height: 100,
+                width: 200,
+                md5: Bytes::from_static(&[0xaa; 16]),
+                size: 1024,
+                url: "https://example.com/image".to_string(),
+                msg_info: Some(MsgInfo::default()),
+                summary: Some("[image]".to_string()),
+                sub_type: 1,
+                is_group: true,
+            })),
+            Entity::Record(dda!(RecordEntity {
+                audio_length: 3,
+                audio_md5: Bytes::from_static(&[0xbb; 16]),
+                audio_name: "record.amr".to_string(),
+                audio_url: "https://example.com/record".to_string(),
+                file_size: 2048,
+                audio_uuid: Some("record_uuid".to_string()),
+                file_sha1: Some("record_sha1".to_string()),
+                msg_info: Some(MsgInfo::default()),
+            })),
+            Entity::Video(dda!(VideoEntity {
+                file_name: "video.mp4".to_string(),
+                video_hash: "video_hash".to_string(),
+                height: 720,
+                width: 1280,
+                video_size: 4096,
+                video_length: 10,
+                video_url: "https://example.com/video".to_string(),
+                node: Some(IndexNode::default()),
+                video_uuid: Some("video_uuid".to_string()),
+                msg_info: Some(MsgInfo::default()),
+            })),
+            Entity::File(dda!(FileEntity {
+                file_size: 8192,
+                file_name: "file.txt".to_string(),
+                file_md5: Bytes::from_static(&[0xcc; 16]),
+                file_url: Some("https://example.com/file".to_string()),
+                file_sha1: Bytes::from_static(&[0xdd; 20]),
+            })),
+            Entity::MultiMsg(MultiMsgEntity {
+                res_id: "res_multi".to_string(),
+                chains: vec![nested_chain("first"), nested_chain("second")],
+                detail_str: Some("detail".to_string()),
+            }),
+            Entity::LongMsg(LongMsgEntity {
+                res_id: "res_long".to_string(),
+                chain: nested_chain("long"),
+            }),
+            Entity::ExtraInfo(ExtraInfoEntity {
+                group_member_special_title: Some("title".to_string()),
+            }),
+            Entity::ExtraGeneralFlags(ExtraGeneralFlagsEntity {
+                permission: GroupMemberPermission::Admin,
+                new_group_level: 5,
+            }),
+            Entity::Unknown(
+                UnknownEntity::from_raw(Bytes::from_static(&[0xa8, 0x1f, 0x01])).unwrap(),
+            ),
+        ]
+    }
+
+    fn sample_chain() -> MessageChain {
+        let mut chain = MessageChain::group(123456);
+        chain.friend_uin = 10001;
+        chain.uid = "u_sender".to_string();
+        chain.message_id = MessageId(114514);
+        chain.sequence = 1919;
+        chain.time = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        chain.elements =
+            vec![Elem::decode([0x0a, 0x04, 0x0a, 0x02, b'h', b'i'].as_slice()).unwrap()];
+        chain.entities = every_entity();
+        chain
+    }
+
+    #[test]
+    fn test_entity_round_trip() {
+        let expected = [
+            "text",
+            "mention",
+            "face",
+            "bounce_face",
+            "poke",
+            "special_poke",
+            "market_face",
+            "json",
+            "xml",
+            "light_app",
+            "markdown",
+            "keyboard",
+            "reply",
+            "image",
+            "record",
+            "video",
+            "file",
+            "multi_msg",
+            "long_msg",
+            "extra_info",
+            "extra_general_flags",
+            "unknown",
+        ];
+        for entity in every_entity() {
+            let json = serde_json::to_value(&entity).unwrap();
+            let restored: Entity = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+        }
+        let tags = every_entity()
+            .iter()
+            .map(|entity| serde_json::to_value(entity).unwrap()["type"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(tags, expected.map(Value::from));
+    }
+
+    #[test]
+    fn test_chain_round_trip() {
+        let chain = sample_chain();
+        let json = serde_json::to_value(&chain).unwrap();
+        assert_eq!(json["version"], SCHEMA_VERSION);
+        let restored: MessageChain = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+
+        match &restored.typ {
+            MessageType::Group(group) => assert_eq!(group.group_uin, 123456),
+            _ => panic!("expected a group chain"),
+        }
+        assert_eq!(restored.elements, chain.elements);
+        assert_eq!(restored.entities.len(), chain.entities.len());
+        let image = restored.entities.iter().find_map(|e| match e {
+            Entity::Image(image) => Some(image),
+            _ => None,
+        });
+        assert!(image.expect("expected an image entity").msg_info.is_some());
+        let unknown = restored.entities.iter().find_map(|e| match e {
+            Entity::Unknown(unknown) => Some(unknown),
+            _ => None,
+        });
+        assert_eq!(
+            unknown
+                .expect("expected an unknown entity")
+                .unknown_fields(),
+            vec![501]
+        );
+    }
+
+    #[test]
+    fn test_version_mismatch_rejected() {
+        let mut json = serde_json::to_value(sample_chain()).unwrap();
+        json["version"] = Value::from(SCHEMA_VERSION + 1);
+        let err = serde_json::from_value::<MessageChain>(json).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unsupported message chain schema version")
+        );
+    }
+}